@@ -1,5 +1,8 @@
 use criterion::{criterion_group, criterion_main, Criterion};
-use rs_bom::BOM;
+use rs_bom::{RangeCollection, BOM};
+
+const WHOLE_BOOK: &str = "1 Ne. 1–22; 2 Ne. 1–33; Jacob 1–7; Enos; Jarom; Omni; W of M; \
+    Mosiah 1–29; Alma 1–63; Hel. 1–16; 3 Ne. 1–30; 4 Ne.; Morm. 1–9; Ether 1–15; Moro. 1–10";
 
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("all verses find ephraim", |b| {
@@ -15,5 +18,26 @@ fn criterion_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, criterion_benchmark);
+fn verse_refs_benchmark(c: &mut Criterion) {
+    let bom = BOM::from_default_parser().unwrap();
+    let whole_book: RangeCollection = WHOLE_BOOK.parse().unwrap();
+
+    c.bench_function("verse refs first verse", |b| {
+        b.iter(|| whole_book.verse_refs(&bom).next())
+    });
+
+    c.bench_function("verse refs len", |b| {
+        b.iter(|| whole_book.verse_refs(&bom).len())
+    });
+
+    c.bench_function("verse refs all verses", |b| {
+        b.iter(|| whole_book.verse_refs(&bom).count())
+    });
+
+    c.bench_function("verse refs all verses reversed", |b| {
+        b.iter(|| whole_book.verse_refs(&bom).rev().count())
+    });
+}
+
+criterion_group!(benches, criterion_benchmark, verse_refs_benchmark);
 criterion_main!(benches);
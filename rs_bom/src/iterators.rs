@@ -1,14 +1,14 @@
-use crate::reference::{VerseReference, Work};
+use crate::reference::VerseReference;
 use crate::{VerseWithReference, BOM};
 use std::iter;
 
 impl BOM {
     /// Iterate over all verses in the entire book.
-    pub fn verses(&self) -> impl Iterator<Item = VerseWithReference> {
+    pub fn verses(&self) -> impl Iterator<Item = VerseWithReference<'_>> {
         VerseIter {
             bom: self,
             position: VerseReference {
-                work: Work::BookOfMormon,
+                work: self.work,
                 book_index: 0,
                 chapter_index: 1,
                 verse_index: 1,
@@ -26,39 +26,43 @@ struct VerseIter<'v> {
 impl<'v> Iterator for VerseIter<'v> {
     type Item = VerseWithReference<'v>;
     fn next(&mut self) -> Option<<Self as iter::Iterator>::Item> {
-        let book = self.bom.books.get(self.position.book_index)?;
-        let chapter = book.chapters.get(self.position.chapter_index - 1)?;
-        let verse = chapter.verses.get(self.position.verse_index - 1)?;
-
-        let result = VerseWithReference {
-            reference: self.position.clone(),
-            book_title: book.short_title.as_ref().unwrap_or(&book.title).clone(),
-            text: &verse.text,
-        };
-
-        self.position.verse_index += 1;
-        if self.position.verse_index > chapter.verses.len() {
-            self.position.verse_index = 1;
-            self.position.chapter_index += 1;
-            if self.position.chapter_index > book.chapters.len() {
+        loop {
+            let book = self.bom.books.get(self.position.book_index)?;
+            let Some(chapter) = book.chapters.get(self.position.chapter_index - 1) else {
+                // Past the last chapter, or a book without any chapters.
+                self.position.book_index += 1;
                 self.position.chapter_index = 1;
-                self.position.book_index += 1; // Any overflow dealt with then they next call next().
-            }
-        }
+                self.position.verse_index = 1;
+                continue;
+            };
+            let Some(verse) = chapter.verses.get(self.position.verse_index - 1) else {
+                self.position.chapter_index += 1;
+                self.position.verse_index = 1;
+                continue;
+            };
 
-        Some(result)
+            let result = VerseWithReference {
+                reference: self.position.clone(),
+                book_title: book.short_title.as_ref().unwrap_or(&book.title).clone(),
+                text: &verse.text,
+            };
+            self.position.verse_index += 1; // Any overflow dealt with on the next call to next().
+            return Some(result);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Book, Chapter, Verse};
+    use crate::reference::Work;
+    use crate::{Book, Chapter, RangeCollection, Verse};
     use std::cmp;
 
     #[test]
     fn empty_verse_iter() {
         let bom = BOM {
+            work: Work::BookOfMormon,
             title: "test title".to_string(),
             subtitle: "test subtitle".to_string(),
             translator: "test translator".to_string(),
@@ -66,6 +70,7 @@ mod tests {
             language: "test language".to_string(),
             title_page_text: "test title page".to_string(),
             witness_testimonies: vec![],
+            chapter_starts: Default::default(),
             books: vec![],
         };
 
@@ -76,6 +81,7 @@ mod tests {
     #[test]
     fn single_book_chapter_verse_iter() {
         let bom = BOM {
+            work: Work::BookOfMormon,
             title: "test title".to_string(),
             subtitle: "test subtitle".to_string(),
             translator: "test translator".to_string(),
@@ -83,6 +89,7 @@ mod tests {
             language: "test language".to_string(),
             title_page_text: "test title page".to_string(),
             witness_testimonies: vec![],
+            chapter_starts: Default::default(),
             books: vec![Book {
                 title: "Testing".to_string(),
                 short_title: None,
@@ -90,7 +97,9 @@ mod tests {
                 chapters: vec![Chapter {
                     verses: vec![Verse {
                         text: "hello".to_string(),
+                        footnotes: vec![],
                     }],
+                    headings: vec![],
                 }],
             }],
         };
@@ -113,9 +122,63 @@ mod tests {
         )
     }
 
+    #[test]
+    fn other_work_verse_iter() {
+        let chapter = |texts: &[&str]| Chapter {
+            verses: texts
+                .iter()
+                .map(|t| Verse {
+                    text: t.to_string(),
+                    footnotes: vec![],
+                })
+                .collect(),
+            headings: vec![],
+        };
+        let bom = BOM {
+            work: Work::OldTestament,
+            title: "test title".to_string(),
+            subtitle: "test subtitle".to_string(),
+            translator: "test translator".to_string(),
+            last_updated: "test updated".to_string(),
+            language: "test language".to_string(),
+            title_page_text: "test title page".to_string(),
+            witness_testimonies: vec![],
+            chapter_starts: Default::default(),
+            books: vec![Book {
+                title: "Genesis".to_string(),
+                short_title: None,
+                description: None,
+                chapters: vec![chapter(&["1:1", "1:2"]), chapter(&["2:1"])],
+            }],
+        };
+
+        assert!(bom.verses().all(|v| v.reference.work == Work::OldTestament));
+        assert_eq!(bom.verses().count(), 3);
+
+        let genesis: RangeCollection = "Gen. 1".parse().unwrap();
+        let verses: Vec<_> = bom.verses_matching(&genesis).collect();
+        assert_eq!(
+            verses
+                .iter()
+                .map(|v| (v.reference.work, v.text))
+                .collect::<Vec<_>>(),
+            vec![(Work::OldTestament, "1:1"), (Work::OldTestament, "1:2")]
+        );
+
+        // The same book, chapter and verse in another work doesn't exist in this copy.
+        let nephi: RangeCollection = "1 Ne. 1".parse().unwrap();
+        assert!(!nephi.is_valid(&bom));
+        assert_eq!(bom.verses_matching(&nephi).count(), 0);
+        let reference = VerseReference::new(Work::BookOfMormon, 0, 1, 1);
+        assert!(!reference.is_valid(&bom));
+        assert!(bom.verse_matching(&reference).is_none());
+        assert!(VerseReference::new(Work::OldTestament, 0, 2, 1).is_valid(&bom));
+    }
+
     #[test]
     fn multiple_book_chapter_verse_iter() {
         let bom = BOM {
+            work: Work::BookOfMormon,
             title: "test title".to_string(),
             subtitle: "test subtitle".to_string(),
             translator: "test translator".to_string(),
@@ -123,6 +186,7 @@ mod tests {
             language: "test language".to_string(),
             title_page_text: "test title page".to_string(),
             witness_testimonies: vec![],
+            chapter_starts: Default::default(),
             books: vec![
                 Book {
                     title: "Testing".to_string(),
@@ -133,21 +197,27 @@ mod tests {
                             verses: vec![
                                 Verse {
                                     text: "hello".to_string(),
+                                    footnotes: vec![],
                                 },
                                 Verse {
                                     text: "hello".to_string(),
+                                    footnotes: vec![],
                                 },
                             ],
+                            headings: vec![],
                         },
                         Chapter {
                             verses: vec![
                                 Verse {
                                     text: "hello".to_string(),
+                                    footnotes: vec![],
                                 },
                                 Verse {
                                     text: "hello".to_string(),
+                                    footnotes: vec![],
                                 },
                             ],
+                            headings: vec![],
                         },
                     ],
                 },
@@ -160,21 +230,27 @@ mod tests {
                             verses: vec![
                                 Verse {
                                     text: "hello".to_string(),
+                                    footnotes: vec![],
                                 },
                                 Verse {
                                     text: "hello".to_string(),
+                                    footnotes: vec![],
                                 },
                             ],
+                            headings: vec![],
                         },
                         Chapter {
                             verses: vec![
                                 Verse {
                                     text: "hello".to_string(),
+                                    footnotes: vec![],
                                 },
                                 Verse {
                                     text: "hello".to_string(),
+                                    footnotes: vec![],
                                 },
                             ],
+                            headings: vec![],
                         },
                     ],
                 },
@@ -190,16 +266,15 @@ mod tests {
         let mut prev_book = 0;
         for v in verses {
             match v.reference.book_index.cmp(&prev_book) {
-                cmp::Ordering::Less => assert!(false, "Next book should be >= previous book"),
+                cmp::Ordering::Less => panic!("Next book should be >= previous book"),
                 cmp::Ordering::Equal => match v.reference.chapter_index.cmp(&prev_chap) {
                     cmp::Ordering::Less => {
-                        assert!(false, "Next chapter should be >= previous chapter")
+                        panic!("Next chapter should be >= previous chapter")
                     }
                     cmp::Ordering::Equal => match v.reference.verse_index.cmp(&prev_verse) {
-                        cmp::Ordering::Less | cmp::Ordering::Equal => assert!(
-                            false,
-                            "In the same chapter, next verse should be >= previous verse"
-                        ),
+                        cmp::Ordering::Less | cmp::Ordering::Equal => {
+                            panic!("In the same chapter, next verse should be >= previous verse")
+                        }
                         _ => {}
                     },
                     _ => {}
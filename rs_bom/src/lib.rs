@@ -1,13 +1,33 @@
+use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use thiserror::Error;
 
 mod iterators;
+mod library;
+mod links;
+mod locale;
 mod parsers;
 mod reference;
+mod scanner;
+mod segments;
+mod versification;
 
-pub use self::parsers::gutenberg;
-pub use self::reference::{RangeCollection, VerseReference, Work};
+pub use self::library::Library;
+pub use self::links::{LinkBuilder, LinkTarget, StudyLinks};
+pub use self::locale::{Locale, UnknownLocale};
+pub use self::parsers::{gutenberg, kjv, usfm};
+pub use self::reference::{
+    structured_ranges, CitationStyle, ClampWarning, ClampWarningKind, FormatOptions, OsisError,
+    ParseOptions, RangeCollection, ReferenceError, ReferenceErrorKind, UnknownCitationStyle,
+    VerseReference, Work,
+};
+pub use self::scanner::ReferenceMatch;
+pub use self::segments::verse_segments;
+pub use self::versification::{StandardVersification, Versification};
+
+use self::reference::part_rank;
+use self::versification::ChapterStarts;
 
 /// Plugin interface for creating a new Book of Mormon parser. Primarily designed
 /// to make it easier to add new languages later.
@@ -21,9 +41,11 @@ pub trait BOMParser {
     fn parse(self) -> Result<BOM, Self::Err>;
 }
 
-/// Represents a parsed copy of the Book of Mormon.
+/// Represents a parsed copy of the Book of Mormon, or of another `Work` with the same
+/// structure of books, chapters and verses.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct BOM {
+    work: Work,
     title: String,
     subtitle: String,
     translator: String,
@@ -32,6 +54,9 @@ pub struct BOM {
     title_page_text: String,
     witness_testimonies: Vec<WitnessTestimony>,
     books: Vec<Book>,
+    /// Filled in on first use, since parsers build the books one verse at a time.
+    #[serde(skip)]
+    chapter_starts: OnceCell<ChapterStarts>,
 }
 
 impl BOM {
@@ -48,20 +73,125 @@ impl BOM {
     }
 
     /// Return an iterator of verses matching the given `RangeCollection`. Any invalid
-    /// verses in the `RangeCollection` are skipped.
-    pub fn verses_matching(
-        &self,
-        range_collection: &RangeCollection,
-    ) -> impl Iterator<Item = VerseWithReference> {
+    /// ranges in the `RangeCollection` are skipped; use `RangeCollection::clamp_to` first to
+    /// keep the verses of them that exist.
+    pub fn verses_matching<'a>(
+        &'a self,
+        range_collection: &'a RangeCollection,
+    ) -> impl Iterator<Item = VerseWithReference<'a>> {
         range_collection
             .verse_refs(self)
             .filter_map(move |i| self.verse_matching(&i))
     }
 
+    /// Like `verses_matching`, but only gives the cited part of verses with a part suffix, such
+    /// as "Alma 32:21b", using the sentences or clauses from `verse_segments`. Parts past the
+    /// last segment of a verse are skipped.
+    pub fn verse_parts_matching<'a>(
+        &'a self,
+        range_collection: &'a RangeCollection,
+    ) -> impl Iterator<Item = VerseWithReference<'a>> {
+        range_collection
+            .verse_refs_with_parts(self)
+            .filter_map(move |(r, start_part, end_part)| {
+                let mut verse = self.verse_matching(&r)?;
+                if start_part.is_none() && end_part.is_none() {
+                    return Some(verse);
+                }
+
+                let bounds = segments::segment_bounds(verse.text);
+                let first = start_part.map_or(0, part_rank);
+                let last = end_part
+                    .map_or(usize::MAX, part_rank)
+                    .min(bounds.len().checked_sub(1)?);
+                if first > last {
+                    return None;
+                }
+                verse.text = &verse.text[bounds[first].start..bounds[last].end];
+                Some(verse)
+            })
+    }
+
+    /// The work this is a copy of. Only references to this work are valid in it.
+    #[must_use]
+    pub const fn work(&self) -> Work {
+        self.work
+    }
+
+    /// Title of this copy, such as "The Book of Mormon".
+    #[must_use]
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Subtitle of this copy, such as "Another Testament of Jesus Christ".
+    #[must_use]
+    pub fn subtitle(&self) -> &str {
+        &self.subtitle
+    }
+
+    /// Who translated this copy.
+    #[must_use]
+    pub fn translator(&self) -> &str {
+        &self.translator
+    }
+
+    /// When the source of this copy was last updated, as written in the source.
+    #[must_use]
+    pub fn last_updated(&self) -> &str {
+        &self.last_updated
+    }
+
+    /// Language of this copy, as written in the source. See `locale` for the `Locale`.
+    #[must_use]
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Text of the title page.
+    #[must_use]
+    pub fn title_page_text(&self) -> &str {
+        &self.title_page_text
+    }
+
+    /// The testimonies of the witnesses, in the order they appear.
+    #[must_use]
+    pub fn witness_testimonies(&self) -> &[WitnessTestimony] {
+        &self.witness_testimonies
+    }
+
+    /// All books, in order. A book's position here is its `book_index` in references.
+    #[must_use]
+    pub fn books(&self) -> &[Book] {
+        &self.books
+    }
+
+    /// The book with the given name, full or abbreviated, in any of the forms or languages
+    /// accepted when parsing references, such as "1 Nephi", "1 Ne." or "Éter".
+    /// ```
+    /// use rs_bom::BOM;
+    ///
+    /// let bom = BOM::from_default_parser().unwrap();
+    /// let book = bom.book_named("moro").unwrap();
+    /// assert_eq!(book.title(), "THE BOOK OF MORONI");
+    /// assert_eq!(book.chapters().len(), 10);
+    /// ```
+    #[must_use]
+    pub fn book_named(&self, name: &str) -> Option<&Book> {
+        let name = reference::normalize_book_name(name);
+        let book_data = Locale::ALL.iter().find_map(|locale| {
+            locale
+                .normalized_book_names()
+                .get(&name)
+                .filter(|d| d.work == self.work)
+        })?;
+        self.books.get(book_data.book_index)
+    }
+
     /// Return a single verse matching the given verse reference. If the reference is invalid,
     /// returns `None`.
     #[must_use]
-    pub fn verse_matching(&self, r: &VerseReference) -> Option<VerseWithReference> {
+    pub fn verse_matching(&self, r: &VerseReference) -> Option<VerseWithReference<'_>> {
         if r.is_valid(self) {
             let book = &self.books[r.book_index];
             let verse = &book.chapters[r.chapter_index - 1].verses[r.verse_index - 1];
@@ -121,38 +251,244 @@ pub enum BOMError {
     },
 
     #[error("Reference error: {0}")]
-    ReferenceError(String),
+    ReferenceError(#[from] ReferenceError),
 }
 
+/// The testimony of a group of witnesses, such as "THE TESTIMONY OF THREE WITNESSES".
 #[derive(Debug, Serialize, Deserialize, Clone)]
-struct WitnessTestimony {
+pub struct WitnessTestimony {
     title: String,
     text: String,
     signatures: String,
 }
 
+impl WitnessTestimony {
+    /// Title of the testimony.
+    #[must_use]
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Text of the testimony.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Names of the witnesses, as written below the testimony.
+    #[must_use]
+    pub fn signatures(&self) -> &str {
+        &self.signatures
+    }
+}
+
+/// A book within a `BOM`, such as 1 Nephi.
 #[derive(Debug, Serialize, Deserialize, Clone)]
-struct Book {
+pub struct Book {
     title: String,
     short_title: Option<String>,
     description: Option<String>,
     chapters: Vec<Chapter>,
 }
 
+impl Book {
+    /// Full title of the book as printed, such as "THE FIRST BOOK OF NEPHI".
+    #[must_use]
+    pub fn title(&self) -> &str {
+        self.title.trim()
+    }
+
+    /// Short title of the book used in references, such as "1 Nephi", if it has one.
+    #[must_use]
+    pub fn short_title(&self) -> Option<&str> {
+        self.short_title.as_deref()
+    }
+
+    /// Introduction printed below the title of some books, such as the account of Lehi's
+    /// journey at the start of 1 Nephi.
+    #[must_use]
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// All chapters, in order. Chapter numbers start at 1, so chapter `n` is at index `n - 1`.
+    #[must_use]
+    pub fn chapters(&self) -> &[Chapter] {
+        &self.chapters
+    }
+
+    /// The chapter numbered `chapter`, counting from 1.
+    #[must_use]
+    pub fn chapter(&self, chapter: usize) -> Option<&Chapter> {
+        self.chapters.get(chapter.checked_sub(1)?)
+    }
+}
+
+/// A chapter within a `Book`.
 #[derive(Debug, Serialize, Deserialize, Clone)]
-struct Chapter {
+pub struct Chapter {
     verses: Vec<Verse>,
+    #[serde(default)]
+    headings: Vec<Heading>,
+}
+
+impl Chapter {
+    /// All verses, in order. Verse numbers start at 1, so verse `n` is at index `n - 1`.
+    #[must_use]
+    pub fn verses(&self) -> &[Verse] {
+        &self.verses
+    }
+
+    /// Section headings within the chapter, in order. Only some sources have headings.
+    #[must_use]
+    pub fn headings(&self) -> &[Heading] {
+        &self.headings
+    }
+
+    /// The verse numbered `verse`, counting from 1.
+    #[must_use]
+    pub fn verse(&self, verse: usize) -> Option<&Verse> {
+        self.verses.get(verse.checked_sub(1)?)
+    }
 }
 
+/// A verse within a `Chapter`.
 #[derive(Debug, Serialize, Deserialize, Clone)]
-struct Verse {
+pub struct Verse {
+    text: String,
+    #[serde(default)]
+    footnotes: Vec<Footnote>,
+}
+
+impl Verse {
+    /// Text of the verse.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Footnotes on the verse, in order. Only some sources have footnotes.
+    #[must_use]
+    pub fn footnotes(&self) -> &[Footnote] {
+        &self.footnotes
+    }
+}
+
+/// A section heading printed between verses, such as "The Creation".
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Heading {
+    level: usize,
+    verse: usize,
+    text: String,
+    #[serde(default)]
+    footnotes: Vec<Footnote>,
+}
+
+impl Heading {
+    /// Level of the heading, from 1 for a main section heading down through minor ones.
+    #[must_use]
+    pub const fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Number of the verse the heading comes before.
+    #[must_use]
+    pub const fn verse(&self) -> usize {
+        self.verse
+    }
+
+    /// Text of the heading.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Notes on the heading, in order. Their offsets are in the heading's text.
+    #[must_use]
+    pub fn footnotes(&self) -> &[Footnote] {
+        &self.footnotes
+    }
+}
+
+/// A note on part of a verse or heading.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Footnote {
+    offset: usize,
+    caller: String,
+    origin: Option<String>,
     text: String,
 }
 
+impl Footnote {
+    /// Byte offset in the verse text that the note is attached to. The note applies to the
+    /// text just before it.
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The mark printed in the text for the note, such as "a". "+" means the publisher picks
+    /// the mark, and "-" means none is printed.
+    #[must_use]
+    pub fn caller(&self) -> &str {
+        &self.caller
+    }
+
+    /// The reference the note was written for, such as "1:2", if the source gives one.
+    #[must_use]
+    pub fn origin(&self) -> Option<&str> {
+        self.origin.as_deref()
+    }
+
+    /// Text of the note.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn structure() {
+        let bom = BOM::from_default_parser().unwrap();
+        assert_eq!(bom.title(), "The Book of Mormon");
+        assert_eq!(bom.witness_testimonies().len(), 2);
+        assert!(bom.witness_testimonies()[0]
+            .signatures()
+            .contains("OLIVER COWDERY"));
+        assert_eq!(bom.books().len(), 15);
+
+        let book = &bom.books()[0];
+        assert_eq!(book.short_title(), Some("1 Nephi"));
+        assert!(book.description().is_some());
+        assert_eq!(book.chapters().len(), 22);
+        assert_eq!(
+            book.chapter(2).and_then(|c| c.verse(15)).map(Verse::text),
+            Some("And my father dwelt in a tent.")
+        );
+        assert!(book.chapter(0).is_none());
+        assert!(book.chapter(23).is_none());
+        assert!(book.chapter(2).unwrap().verse(0).is_none());
+
+        for (name, title) in [
+            ("1 Nephi", "THE FIRST BOOK OF NEPHI"),
+            ("W of M", "THE WORDS OF MORMON"),
+            ("Éter", "THE BOOK OF ETHER"),
+        ] {
+            assert_eq!(
+                bom.book_named(name).map(Book::title),
+                Some(title),
+                "{}",
+                name
+            );
+        }
+        assert!(bom.book_named("Genesis").is_none());
+        assert!(bom.book_named("Ephraim").is_none());
+    }
+
     #[test]
     fn verse_matching_bad_reference() {
         let bom = BOM::from_default_parser().unwrap();
@@ -261,6 +597,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn verses_matching_good_reference_chapter_verse_ranges() {
+        let bom = BOM::from_default_parser().unwrap();
+        let reference = "1 Nephi 1:20–2:3".parse::<RangeCollection>().unwrap();
+        let references: Vec<_> = bom
+            .verses_matching(&reference)
+            .map(|v| (v.reference.chapter_index, v.reference.verse_index))
+            .collect();
+        assert_eq!(references, vec![(1, 20), (2, 1), (2, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn verse_parts_matching() {
+        let bom = BOM::from_default_parser().unwrap();
+        let cases: [(&str, &[&str]); 5] = [
+            ("1 Ne. 2:15", &["And my father dwelt in a tent."]),
+            (
+                "1 Ne. 3:7a",
+                &["And it came to pass that I, Nephi, said unto my father:"],
+            ),
+            (
+                "Alma 32:21a",
+                &["And now as I said concerning faith--faith is not to have a perfect knowledge of things;"],
+            ),
+            (
+                "Alma 32:21b–22a",
+                &[
+                    "therefore if ye have faith ye hope for things which are not seen, which are true.",
+                    "And now, behold, I say unto you, and I would that ye should remember, that God is \
+                    merciful unto all who believe on his name;",
+                ],
+            ),
+            ("Alma 32:21e", &[]),
+        ];
+        for (input, expected) in cases {
+            let reference: RangeCollection = input.parse().unwrap();
+            let texts: Vec<_> = bom
+                .verse_parts_matching(&reference)
+                .map(|v| v.text)
+                .collect();
+            assert_eq!(texts, expected, "{}", input);
+        }
+    }
+
     #[test]
     fn verses_matching_good_reference_chapter_ranges() {
         let bom = BOM::from_default_parser().unwrap();
@@ -0,0 +1,199 @@
+use crate::reference::{RangeCollection, VerseReference, Work};
+use crate::{VerseWithReference, Versification, BOM};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Several parsed works, such as the Bible and the Book of Mormon, so that references to any
+/// of them can be resolved together. Each work is held as its own `BOM`, and references are
+/// routed to it by their `Work`.
+/// ```no_run
+/// use rs_bom::{kjv, BOMParser, Library, RangeCollection, Work, BOM};
+/// use std::path::Path;
+///
+/// let old_testament = kjv::Parser::new(Path::new("kjv.txt"), Work::OldTestament);
+/// let library = Library::new()
+///     .with_work(BOM::from_default_parser().unwrap())
+///     .with_work(old_testament.parse().unwrap());
+/// let references: RangeCollection = "Isa. 29:14; 2 Ne. 25:17".parse().unwrap();
+/// for verse in library.verses_matching(&references) {
+///     println!("{}", verse);
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Library {
+    works: BTreeMap<Work, BOM>,
+}
+
+impl Library {
+    /// A library without any works.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `corpus` as the text of its work, replacing any text already held for it.
+    #[must_use]
+    pub fn with_work(mut self, corpus: BOM) -> Self {
+        self.insert(corpus);
+        self
+    }
+
+    /// Add `corpus` as the text of its work, returning the text it replaced, if any.
+    pub fn insert(&mut self, corpus: BOM) -> Option<BOM> {
+        self.works.insert(corpus.work(), corpus)
+    }
+
+    /// The text held for `work`, if any.
+    #[must_use]
+    pub fn get(&self, work: Work) -> Option<&BOM> {
+        self.works.get(&work)
+    }
+
+    /// The works held, in canonical order.
+    pub fn works(&self) -> impl Iterator<Item = Work> + '_ {
+        self.works.keys().copied()
+    }
+
+    /// Iterate over every verse of every work, in canonical order.
+    pub fn verses(&self) -> impl Iterator<Item = VerseWithReference<'_>> {
+        self.works.values().flat_map(BOM::verses)
+    }
+
+    /// Return an iterator of verses matching the given `RangeCollection`, from whichever
+    /// work each range cites. Ranges in works that aren't held, or that are otherwise
+    /// invalid, are skipped.
+    pub fn verses_matching<'a>(
+        &'a self,
+        range_collection: &'a RangeCollection,
+    ) -> impl Iterator<Item = VerseWithReference<'a>> {
+        range_collection
+            .verse_refs(self)
+            .filter_map(move |r| self.verse_matching(&r))
+    }
+
+    /// Return a single verse matching the given verse reference, from the work it cites. If
+    /// the work isn't held or the reference is invalid, returns `None`.
+    #[must_use]
+    pub fn verse_matching(&self, r: &VerseReference) -> Option<VerseWithReference<'_>> {
+        self.works.get(&r.work)?.verse_matching(r)
+    }
+}
+
+impl Versification for Library {
+    fn chapter_count(&self, work: Work, book_index: usize) -> Option<usize> {
+        self.works.get(&work)?.chapter_count(work, book_index)
+    }
+
+    fn verse_count(&self, work: Work, book_index: usize, chapter: usize) -> Option<usize> {
+        self.works
+            .get(&work)?
+            .verse_count(work, book_index, chapter)
+    }
+
+    fn verses_before(&self, work: Work, book_index: usize, chapter: usize) -> Option<usize> {
+        self.works
+            .get(&work)?
+            .verses_before(work, book_index, chapter)
+    }
+
+    fn chapter_at(&self, work: Work, ordinal: usize) -> Option<(usize, usize, usize)> {
+        self.works.get(&work)?.chapter_at(work, ordinal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Book, Chapter, Verse};
+
+    /// A copy of `work` with `books` books, each with `chapters` chapters of `verses` verses,
+    /// whose text is its position, like "23 29:14".
+    fn synthetic_corpus(work: Work, books: usize, chapters: usize, verses: usize) -> BOM {
+        BOM {
+            work,
+            title: "Synthetic".to_string(),
+            subtitle: String::new(),
+            translator: String::new(),
+            last_updated: String::new(),
+            language: "en".to_string(),
+            title_page_text: String::new(),
+            witness_testimonies: vec![],
+            chapter_starts: Default::default(),
+            books: (1..=books)
+                .map(|b| Book {
+                    title: format!("Book {}", b),
+                    short_title: None,
+                    description: None,
+                    chapters: (1..=chapters)
+                        .map(|c| Chapter {
+                            verses: (1..=verses)
+                                .map(|v| Verse {
+                                    text: format!("{} {}:{}", b, c, v),
+                                    footnotes: vec![],
+                                })
+                                .collect(),
+                            headings: vec![],
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn verses_across_works() {
+        let library = Library::new()
+            .with_work(BOM::from_default_parser().unwrap())
+            .with_work(synthetic_corpus(Work::OldTestament, 23, 30, 20));
+        let references: RangeCollection = "Isa. 29:14; 2 Ne. 25:17".parse().unwrap();
+        let verses: Vec<_> = library.verses_matching(&references).collect();
+        assert_eq!(verses.len(), 2);
+        assert_eq!(verses[0].reference.work, Work::OldTestament);
+        assert_eq!(verses[0].text, "23 29:14");
+        assert_eq!(verses[1].reference.work, Work::BookOfMormon);
+        assert!(verses[1]
+            .text
+            .starts_with("And the Lord will set his hand again"));
+
+        // Works that aren't held are skipped.
+        let references: RangeCollection = "Matt. 5:3; Gen. 1:1; Gen. 31:1".parse().unwrap();
+        let verses: Vec<_> = library.verses_matching(&references).collect();
+        assert_eq!(verses.len(), 1);
+        assert_eq!(verses[0].text, "1 1:1");
+        assert!(!references.is_valid(&library));
+    }
+
+    #[test]
+    fn works_and_verses() {
+        let mut library = Library::new();
+        assert!(library.verses().next().is_none());
+        assert!(library
+            .insert(synthetic_corpus(Work::NewTestament, 1, 1, 2))
+            .is_none());
+        library.insert(synthetic_corpus(Work::OldTestament, 1, 2, 1));
+        assert!(library
+            .insert(synthetic_corpus(Work::NewTestament, 1, 1, 3))
+            .is_some());
+
+        assert_eq!(
+            library.works().collect::<Vec<_>>(),
+            vec![Work::OldTestament, Work::NewTestament]
+        );
+        let verses: Vec<_> = library
+            .verses()
+            .map(|v| (v.reference.work, v.text))
+            .collect();
+        assert_eq!(
+            verses,
+            vec![
+                (Work::OldTestament, "1 1:1"),
+                (Work::OldTestament, "1 2:1"),
+                (Work::NewTestament, "1 1:1"),
+                (Work::NewTestament, "1 1:2"),
+                (Work::NewTestament, "1 1:3"),
+            ]
+        );
+        assert_eq!(library.get(Work::NewTestament).unwrap().books().len(), 1);
+        assert!(library.get(Work::BookOfMormon).is_none());
+    }
+}
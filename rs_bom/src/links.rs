@@ -0,0 +1,309 @@
+use crate::reference::{BookData, RangeCollection, Work, BOOK_DATA};
+use std::{fmt, ops::RangeInclusive};
+
+/// One linkable part of a `RangeCollection`: a whole book, a whole chapter, or some verses
+/// within a single chapter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkTarget {
+    pub(super) work: Work,
+    pub(super) book_index: usize,
+    pub(super) chapter: Option<usize>,
+    pub(super) verses: Vec<RangeInclusive<usize>>,
+}
+
+impl LinkTarget {
+    /// The work containing the book.
+    #[must_use]
+    pub const fn work(&self) -> Work {
+        self.work
+    }
+
+    /// 0-based index of the book within its work.
+    #[must_use]
+    pub const fn book_index(&self) -> usize {
+        self.book_index
+    }
+
+    /// The chapter, or `None` if the whole book is linked.
+    #[must_use]
+    pub const fn chapter(&self) -> Option<usize> {
+        self.chapter
+    }
+
+    /// Ranges of verses in the chapter, in citation order. Empty if the whole chapter or book
+    /// is linked.
+    #[must_use]
+    pub fn verses(&self) -> &[RangeInclusive<usize>] {
+        &self.verses
+    }
+
+    fn book_data(&self) -> &'static BookData {
+        // Targets are only created from parsed references, so the book always exists.
+        BOOK_DATA
+            .iter()
+            .find(|d| d.work == self.work && d.book_index == self.book_index)
+            .unwrap()
+    }
+
+    /// Book name used in churchofjesuschrist.org URLs, such as "1-ne".
+    #[must_use]
+    pub fn url_book_name(&self) -> &'static str {
+        self.book_data().url_name
+    }
+}
+
+/// Written as a citation, such as "Alma 32:21–23, 27".
+impl fmt::Display for LinkTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.book_data().short_name)?;
+        let Some(chapter) = self.chapter else {
+            return Ok(());
+        };
+        write!(f, " {}", chapter)?;
+        for (i, verses) in self.verses.iter().enumerate() {
+            write!(f, "{}", if i == 0 { ":" } else { ", " })?;
+            if verses.start() == verses.end() {
+                write!(f, "{}", verses.start())?;
+            } else {
+                write!(f, "{}–{}", verses.start(), verses.end())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Builds the link for each `LinkTarget` of a `RangeCollection`. Implemented by
+/// `StudyLinks` and by any `Fn(&LinkTarget) -> String`, so links can point anywhere, such
+/// as a self-hosted `rs_bom_api`:
+/// ```
+/// use rs_bom::{LinkTarget, RangeCollection};
+///
+/// let reader = |target: &LinkTarget| {
+///     format!("https://bom.example.com/verses/{}", target.to_string().replace(' ', "%20"))
+/// };
+/// let references: RangeCollection = "Alma 32:21, 27; Moro. 10:4–5".parse().unwrap();
+/// assert_eq!(
+///     references.urls_with(&reader),
+///     vec![
+///         "https://bom.example.com/verses/Alma%2032:21,%2027",
+///         "https://bom.example.com/verses/Moro.%2010:4–5",
+///     ]
+/// );
+/// ```
+pub trait LinkBuilder {
+    /// The link for `target`.
+    fn link(&self, target: &LinkTarget) -> String;
+}
+
+impl<F: Fn(&LinkTarget) -> String> LinkBuilder for F {
+    fn link(&self, target: &LinkTarget) -> String {
+        self(target)
+    }
+}
+
+/// Links to the scriptures on churchofjesuschrist.org, such as
+/// "https://www.churchofjesuschrist.org/study/scriptures/bofm/alma/32?lang=eng&id=p21-p23,p27-p27#p21".
+/// The base URL and `lang` parameter can be changed, e.g. to link to a mirror or to another
+/// language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StudyLinks {
+    base: String,
+    lang: String,
+}
+
+impl StudyLinks {
+    const DEFAULT_BASE: &'static str = "https://www.churchofjesuschrist.org/study/scriptures";
+    const DEFAULT_LANG: &'static str = "eng";
+
+    /// English links to churchofjesuschrist.org.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `base` in place of "https://www.churchofjesuschrist.org/study/scriptures".
+    #[must_use]
+    pub fn with_base(mut self, base: impl Into<String>) -> Self {
+        self.base = base.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Use `lang` as the language code, such as "spa" or "por".
+    #[must_use]
+    pub fn with_lang(mut self, lang: impl Into<String>) -> Self {
+        self.lang = lang.into();
+        self
+    }
+}
+
+impl Default for StudyLinks {
+    fn default() -> Self {
+        Self {
+            base: Self::DEFAULT_BASE.to_string(),
+            lang: Self::DEFAULT_LANG.to_string(),
+        }
+    }
+}
+
+impl LinkBuilder for StudyLinks {
+    fn link(&self, target: &LinkTarget) -> String {
+        let mut url = format!(
+            "{}/{}/{}",
+            self.base,
+            target.work.url_name(),
+            target.url_book_name()
+        );
+        if let Some(chapter) = target.chapter {
+            url += &format!("/{}", chapter);
+        }
+        url += &format!("?lang={}", self.lang);
+
+        if let Some(first) = target.verses.first() {
+            let ids: Vec<_> = target
+                .verses
+                .iter()
+                .map(|v| format!("p{}-p{}", v.start(), v.end()))
+                .collect();
+            url += &format!("&id={}#p{}", ids.join(","), first.start());
+        }
+        url
+    }
+}
+
+impl RangeCollection {
+    /// One churchofjesuschrist.org link for each book, chapter, or set of verses within a
+    /// chapter cited in this collection. See `link_targets` for how the collection is split
+    /// up.
+    /// ```
+    /// use rs_bom::RangeCollection;
+    ///
+    /// let references: RangeCollection = "Alma 32:21, 27; 33".parse().unwrap();
+    /// let base = "https://www.churchofjesuschrist.org/study/scriptures/bofm/alma";
+    /// assert_eq!(
+    ///     references.urls(),
+    ///     vec![
+    ///         format!("{}/32?lang=eng&id=p21-p21,p27-p27#p21", base),
+    ///         format!("{}/33?lang=eng", base),
+    ///     ]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn urls(&self) -> Vec<String> {
+        self.urls_with(&StudyLinks::default())
+    }
+
+    /// One link from `builder` for each of the `link_targets` in this collection.
+    #[must_use]
+    pub fn urls_with(&self, builder: &dyn LinkBuilder) -> Vec<String> {
+        self.link_targets()
+            .iter()
+            .map(|target| builder.link(target))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "https://www.churchofjesuschrist.org/study/scriptures";
+
+    #[test]
+    fn study_urls() {
+        let cases: [(&str, &[&str]); 9] = [
+            ("1 Ne. 3:7", &["/bofm/1-ne/3?lang=eng&id=p7-p7#p7"]),
+            (
+                "Alma 32:21–23, 27",
+                &["/bofm/alma/32?lang=eng&id=p21-p23,p27-p27#p21"],
+            ),
+            ("Alma 32", &["/bofm/alma/32?lang=eng"]),
+            (
+                "Alma 32–34",
+                &[
+                    "/bofm/alma/32?lang=eng",
+                    "/bofm/alma/33?lang=eng",
+                    "/bofm/alma/34?lang=eng",
+                ],
+            ),
+            (
+                "Alma 3:5–4:2",
+                &[
+                    "/bofm/alma/3?lang=eng&id=p5-p27#p5",
+                    "/bofm/alma/4?lang=eng&id=p1-p2#p1",
+                ],
+            ),
+            (
+                "Alma 3:5–5:2",
+                &[
+                    "/bofm/alma/3?lang=eng&id=p5-p27#p5",
+                    "/bofm/alma/4?lang=eng",
+                    "/bofm/alma/5?lang=eng&id=p1-p2#p1",
+                ],
+            ),
+            ("Moro. 10:30ff", &["/bofm/moro/10?lang=eng&id=p30-p34#p30"]),
+            (
+                "Moro. 9ff; Enos",
+                &[
+                    "/bofm/moro/9?lang=eng",
+                    "/bofm/moro/10?lang=eng",
+                    "/bofm/enos?lang=eng",
+                ],
+            ),
+            (
+                "D&C 121:7–8; JS—H 1:17",
+                &[
+                    "/dc-testament/dc/121?lang=eng&id=p7-p8#p7",
+                    "/pgp/js-h/1?lang=eng&id=p17-p17#p17",
+                ],
+            ),
+        ];
+        for (input, expected) in cases {
+            let parsed: RangeCollection = input.parse().unwrap();
+            let expected: Vec<_> = expected.iter().map(|e| format!("{}{}", BASE, e)).collect();
+            assert_eq!(parsed.urls(), expected, "{}", input);
+
+            // Each link reads back as the part of the collection it covers.
+            for (url, target) in parsed.urls().iter().zip(parsed.link_targets()) {
+                let read_back = RangeCollection::from_url(url).unwrap();
+                assert_eq!(read_back.to_string(), target.to_string(), "{}", url);
+            }
+        }
+    }
+
+    #[test]
+    fn empty_collection_has_no_urls() {
+        let empty: RangeCollection = "Alma 3"
+            .parse::<RangeCollection>()
+            .unwrap()
+            .difference(&"Alma 3".parse().unwrap(), &crate::StandardVersification);
+        assert!(empty.urls().is_empty());
+    }
+
+    #[test]
+    fn custom_links() {
+        let references: RangeCollection = "Alma 32:21; Moro. 10".parse().unwrap();
+        let spanish = StudyLinks::new()
+            .with_base("https://mirror.example.com/scriptures/")
+            .with_lang("spa");
+        assert_eq!(
+            references.urls_with(&spanish),
+            vec![
+                "https://mirror.example.com/scriptures/bofm/alma/32?lang=spa&id=p21-p21#p21",
+                "https://mirror.example.com/scriptures/bofm/moro/10?lang=spa",
+            ]
+        );
+
+        let reader = |target: &LinkTarget| {
+            format!(
+                "/read/{}/{}/{}",
+                target.url_book_name(),
+                target.chapter().unwrap_or_default(),
+                target.verses().len()
+            )
+        };
+        assert_eq!(
+            references.urls_with(&reader),
+            vec!["/read/alma/32/1", "/read/moro/10/0"]
+        );
+    }
+}
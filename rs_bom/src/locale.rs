@@ -0,0 +1,448 @@
+use crate::reference::{normalize_book_name, BookData, BOOK_DATA, NORMALIZED_BOOK_NAMES};
+use crate::BOM;
+use once_cell::sync::Lazy;
+use std::{collections::HashMap, ptr, str};
+use thiserror::Error;
+
+/// A language that book names can be parsed and written in.
+///
+/// Parse with `ParseOptions::with_locale` or `ParseOptions::with_any_locale`, and write with
+/// `FormatOptions::with_locale`:
+/// ```
+/// use rs_bom::{FormatOptions, Locale, ParseOptions, RangeCollection};
+///
+/// let spanish = ParseOptions::new().with_locale(Locale::Spanish);
+/// let references = RangeCollection::parse_with("1 Nefi 3:7; Éter 12:27", &spanish).unwrap();
+/// assert_eq!(references.to_string(), "1 Ne. 3:7; Ether 12:27");
+///
+/// let german = FormatOptions::new().with_locale(Locale::German);
+/// assert_eq!(references.display_with(german).to_string(), "1 Ne 3:7; Ether 12:27");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    #[default]
+    English,
+    Spanish,
+    Portuguese,
+    French,
+    German,
+}
+
+impl Locale {
+    /// Every supported locale, in the order they're tried when detecting the locale.
+    pub const ALL: [Self; 5] = [
+        Self::English,
+        Self::Spanish,
+        Self::Portuguese,
+        Self::French,
+        Self::German,
+    ];
+
+    /// ISO 639-1 code of the language, such as "es".
+    #[must_use]
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::Spanish => "es",
+            Self::Portuguese => "pt",
+            Self::French => "fr",
+            Self::German => "de",
+        }
+    }
+
+    /// Full and abbreviated names of every book in this language, in the same order as
+    /// `BOOK_DATA`. English names come from `BOOK_DATA` itself.
+    const fn names(self) -> Option<&'static [(&'static str, &'static str)]> {
+        match self {
+            Self::English => None,
+            Self::Spanish => Some(&SPANISH),
+            Self::Portuguese => Some(&PORTUGUESE),
+            Self::French => Some(&FRENCH),
+            Self::German => Some(&GERMAN),
+        }
+    }
+
+    fn book_names(self, book_data: &BookData) -> (&'static str, &'static str) {
+        let names = self.names().and_then(|names| {
+            let position = BOOK_DATA.iter().position(|d| ptr::eq(d, book_data))?;
+            names.get(position).copied()
+        });
+        names.unwrap_or((book_data.long_name, book_data.short_name))
+    }
+
+    /// Full name of a book in this language, such as "1 Nefi".
+    pub(crate) fn long_name(self, book_data: &BookData) -> &'static str {
+        self.book_names(book_data).0
+    }
+
+    /// Abbreviated name of a book in this language, such as "1 Ne.".
+    pub(crate) fn short_name(self, book_data: &BookData) -> &'static str {
+        self.book_names(book_data).1
+    }
+
+    /// Every book keyed by its normalized names in this language.
+    pub(crate) fn normalized_book_names(self) -> &'static HashMap<String, &'static BookData> {
+        static LOCALIZED: Lazy<HashMap<Locale, HashMap<String, &'static BookData>>> =
+            Lazy::new(|| {
+                Locale::ALL
+                    .iter()
+                    .filter_map(|&locale| {
+                        let names = locale.names()?;
+                        let map = BOOK_DATA
+                            .iter()
+                            .zip(names)
+                            .flat_map(|(d, (long, short))| {
+                                [long, short].map(|name| (normalize_book_name(name), d))
+                            })
+                            .collect();
+                        Some((locale, map))
+                    })
+                    .collect()
+            });
+
+        match self {
+            Self::English => &NORMALIZED_BOOK_NAMES,
+            _ => &LOCALIZED[&self],
+        }
+    }
+}
+
+/// Error returned when a `Locale` name or code isn't recognized.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Unknown locale \"{0}\"")]
+pub struct UnknownLocale(String);
+
+impl str::FromStr for Locale {
+    type Err = UnknownLocale;
+
+    /// Parse a locale from its ISO 639-1 or 639-2 code ("es", "spa") or English name
+    /// ("Spanish"), ignoring case and any region ("es-MX").
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_lowercase();
+        let language = lower.split(['-', '_']).next().unwrap_or_default();
+        match language {
+            "en" | "eng" | "english" => Ok(Self::English),
+            "es" | "spa" | "spanish" => Ok(Self::Spanish),
+            "pt" | "por" | "portuguese" => Ok(Self::Portuguese),
+            "fr" | "fra" | "fre" | "french" => Ok(Self::French),
+            "de" | "deu" | "ger" | "german" => Ok(Self::German),
+            _ => Err(UnknownLocale(s.to_string())),
+        }
+    }
+}
+
+impl BOM {
+    /// The locale matching this copy's language, if it's one we have book names for.
+    #[must_use]
+    pub fn locale(&self) -> Option<Locale> {
+        self.language.parse().ok()
+    }
+}
+
+#[rustfmt::skip]
+const SPANISH: [(&str, &str); 87] = [
+    ("Génesis", "Gén."), ("Éxodo", "Éx."), ("Levítico", "Lev."), ("Números", "Núm."),
+    ("Deuteronomio", "Deut."), ("Josué", "Josué"), ("Jueces", "Jue."), ("Rut", "Rut"),
+    ("1 Samuel", "1 Sam."), ("2 Samuel", "2 Sam."), ("1 Reyes", "1 Rey."), ("2 Reyes", "2 Rey."),
+    ("1 Crónicas", "1 Cró."), ("2 Crónicas", "2 Cró."), ("Esdras", "Esd."), ("Nehemías", "Neh."),
+    ("Ester", "Ester"), ("Job", "Job"), ("Salmos", "Sal."), ("Proverbios", "Prov."),
+    ("Eclesiastés", "Ecle."), ("Cantares", "Cant."), ("Isaías", "Isa."), ("Jeremías", "Jer."),
+    ("Lamentaciones", "Lam."), ("Ezequiel", "Ezeq."), ("Daniel", "Dan."), ("Oseas", "Os."),
+    ("Joel", "Joel"), ("Amós", "Amós"), ("Abdías", "Abd."), ("Jonás", "Jon."),
+    ("Miqueas", "Miq."), ("Nahúm", "Nah."), ("Habacuc", "Hab."), ("Sofonías", "Sof."),
+    ("Hageo", "Hageo"), ("Zacarías", "Zac."), ("Malaquías", "Mal."),
+
+    ("Mateo", "Mateo"), ("Marcos", "Mar."), ("Lucas", "Lucas"), ("Juan", "Juan"),
+    ("Hechos", "Hech."), ("Romanos", "Rom."), ("1 Corintios", "1 Cor."), ("2 Corintios", "2 Cor."),
+    ("Gálatas", "Gál."), ("Efesios", "Efe."), ("Filipenses", "Filip."), ("Colosenses", "Col."),
+    ("1 Tesalonicenses", "1 Tes."), ("2 Tesalonicenses", "2 Tes."), ("1 Timoteo", "1 Tim."),
+    ("2 Timoteo", "2 Tim."), ("Tito", "Tito"), ("Filemón", "Filem."), ("Hebreos", "Heb."),
+    ("Santiago", "Sant."), ("1 Pedro", "1 Pe."), ("2 Pedro", "2 Pe."), ("1 Juan", "1 Juan"),
+    ("2 Juan", "2 Juan"), ("3 Juan", "3 Juan"), ("Judas", "Judas"), ("Apocalipsis", "Apoc."),
+
+    ("1 Nefi", "1 Ne."), ("2 Nefi", "2 Ne."), ("Jacob", "Jacob"), ("Enós", "Enós"),
+    ("Jarom", "Jarom"), ("Omni", "Omni"), ("Palabras de Mormón", "P. de Morm."),
+    ("Mosíah", "Mos."), ("Alma", "Alma"), ("Helamán", "Hel."), ("3 Nefi", "3 Ne."),
+    ("4 Nefi", "4 Ne."), ("Mormón", "Morm."), ("Éter", "Éter"), ("Moroni", "Moro."),
+
+    ("Doctrina y Convenios", "DyC"),
+
+    ("Moisés", "Moisés"), ("Abraham", "Abr."), ("José Smith—Mateo", "JS—M"),
+    ("José Smith—Historia", "JS—H"), ("Artículos de Fe", "AdeF"),
+];
+
+#[rustfmt::skip]
+const PORTUGUESE: [(&str, &str); 87] = [
+    ("Gênesis", "Gên."), ("Êxodo", "Êx."), ("Levítico", "Lev."), ("Números", "Núm."),
+    ("Deuteronômio", "Deut."), ("Josué", "Jos."), ("Juízes", "Juí."), ("Rute", "Rute"),
+    ("1 Samuel", "1 Sam."), ("2 Samuel", "2 Sam."), ("1 Reis", "1 Reis"), ("2 Reis", "2 Reis"),
+    ("1 Crônicas", "1 Crôn."), ("2 Crônicas", "2 Crôn."), ("Esdras", "Esd."), ("Neemias", "Nee."),
+    ("Ester", "Ester"), ("Jó", "Jó"), ("Salmos", "Sal."), ("Provérbios", "Prov."),
+    ("Eclesiastes", "Ecl."), ("Cantares", "Cant."), ("Isaías", "Isa."), ("Jeremias", "Jer."),
+    ("Lamentações", "Lam."), ("Ezequiel", "Ez."), ("Daniel", "Dan."), ("Oseias", "Os."),
+    ("Joel", "Joel"), ("Amós", "Amós"), ("Obadias", "Obad."), ("Jonas", "Jonas"),
+    ("Miqueias", "Miq."), ("Naum", "Naum"), ("Habacuque", "Hab."), ("Sofonias", "Sof."),
+    ("Ageu", "Ageu"), ("Zacarias", "Zac."), ("Malaquias", "Mal."),
+
+    ("Mateus", "Mat."), ("Marcos", "Mar."), ("Lucas", "Luc."), ("João", "João"),
+    ("Atos", "Atos"), ("Romanos", "Rom."), ("1 Coríntios", "1 Cor."), ("2 Coríntios", "2 Cor."),
+    ("Gálatas", "Gál."), ("Efésios", "Ef."), ("Filipenses", "Filip."), ("Colossenses", "Col."),
+    ("1 Tessalonicenses", "1 Tess."), ("2 Tessalonicenses", "2 Tess."), ("1 Timóteo", "1 Tim."),
+    ("2 Timóteo", "2 Tim."), ("Tito", "Tito"), ("Filemom", "Filem."), ("Hebreus", "Heb."),
+    ("Tiago", "Tia."), ("1 Pedro", "1 Ped."), ("2 Pedro", "2 Ped."), ("1 João", "1 João"),
+    ("2 João", "2 João"), ("3 João", "3 João"), ("Judas", "Judas"), ("Apocalipse", "Apoc."),
+
+    ("1 Néfi", "1 Né."), ("2 Néfi", "2 Né."), ("Jacó", "Jacó"), ("Enos", "Enos"),
+    ("Jarom", "Jarom"), ("Ômni", "Ômni"), ("Palavras de Mórmon", "P. de Mórm."),
+    ("Mosias", "Mosias"), ("Alma", "Alma"), ("Helamã", "Hel."), ("3 Néfi", "3 Né."),
+    ("4 Néfi", "4 Né."), ("Mórmon", "Mórm."), ("Éter", "Éter"), ("Morôni", "Morô."),
+
+    ("Doutrina e Convênios", "D&C"),
+
+    ("Moisés", "Moisés"), ("Abraão", "Abr."), ("Joseph Smith—Mateus", "JS—M"),
+    ("Joseph Smith—História", "JS—H"), ("Regras de Fé", "RF"),
+];
+
+#[rustfmt::skip]
+const FRENCH: [(&str, &str); 87] = [
+    ("Genèse", "Gn"), ("Exode", "Ex"), ("Lévitique", "Lv"), ("Nombres", "Nb"),
+    ("Deutéronome", "Dt"), ("Josué", "Jos"), ("Juges", "Jg"), ("Ruth", "Rt"),
+    ("1 Samuel", "1 S"), ("2 Samuel", "2 S"), ("1 Rois", "1 R"), ("2 Rois", "2 R"),
+    ("1 Chroniques", "1 Ch"), ("2 Chroniques", "2 Ch"), ("Esdras", "Esd"), ("Néhémie", "Né"),
+    ("Esther", "Est"), ("Job", "Jb"), ("Psaumes", "Ps"), ("Proverbes", "Pr"),
+    ("Ecclésiaste", "Ec"), ("Cantique des cantiques", "Ct"), ("Ésaïe", "És"), ("Jérémie", "Jr"),
+    ("Lamentations", "Lm"), ("Ézéchiel", "Ez"), ("Daniel", "Dn"), ("Osée", "Os"),
+    ("Joël", "Jl"), ("Amos", "Am"), ("Abdias", "Ab"), ("Jonas", "Jon"),
+    ("Michée", "Mi"), ("Nahum", "Na"), ("Habakuk", "Ha"), ("Sophonie", "So"),
+    ("Aggée", "Ag"), ("Zacharie", "Za"), ("Malachie", "Ml"),
+
+    ("Matthieu", "Mt"), ("Marc", "Mc"), ("Luc", "Lc"), ("Jean", "Jn"),
+    ("Actes", "Ac"), ("Romains", "Rm"), ("1 Corinthiens", "1 Co"), ("2 Corinthiens", "2 Co"),
+    ("Galates", "Ga"), ("Éphésiens", "Ép"), ("Philippiens", "Ph"), ("Colossiens", "Col"),
+    ("1 Thessaloniciens", "1 Th"), ("2 Thessaloniciens", "2 Th"), ("1 Timothée", "1 Tm"),
+    ("2 Timothée", "2 Tm"), ("Tite", "Tt"), ("Philémon", "Phm"), ("Hébreux", "Hé"),
+    ("Jacques", "Jc"), ("1 Pierre", "1 P"), ("2 Pierre", "2 P"), ("1 Jean", "1 Jn"),
+    ("2 Jean", "2 Jn"), ("3 Jean", "3 Jn"), ("Jude", "Jud"), ("Apocalypse", "Ap"),
+
+    ("1 Néphi", "1 Né"), ("2 Néphi", "2 Né"), ("Jacob", "Jacob"), ("Énos", "Énos"),
+    ("Jarom", "Jarom"), ("Omni", "Omni"), ("Paroles de Mormon", "P de M"),
+    ("Mosiah", "Mosiah"), ("Alma", "Alma"), ("Hélaman", "Hél"), ("3 Néphi", "3 Né"),
+    ("4 Néphi", "4 Né"), ("Mormon", "Mrm"), ("Éther", "Éth"), ("Moroni", "Mro"),
+
+    ("Doctrine et Alliances", "D&A"),
+
+    ("Moïse", "Moï"), ("Abraham", "Abr"), ("Joseph Smith—Matthieu", "JS—M"),
+    ("Joseph Smith—Histoire", "JS—H"), ("Articles de foi", "AF"),
+];
+
+#[rustfmt::skip]
+const GERMAN: [(&str, &str); 87] = [
+    ("Genesis", "Gen"), ("Exodus", "Ex"), ("Levitikus", "Lev"), ("Numeri", "Num"),
+    ("Deuteronomium", "Dtn"), ("Josua", "Jos"), ("Richter", "Ri"), ("Rut", "Rut"),
+    ("1 Samuel", "1 Sam"), ("2 Samuel", "2 Sam"), ("1 Könige", "1 Kön"), ("2 Könige", "2 Kön"),
+    ("1 Chronik", "1 Chr"), ("2 Chronik", "2 Chr"), ("Esra", "Esra"), ("Nehemia", "Neh"),
+    ("Ester", "Est"), ("Ijob", "Ijob"), ("Psalmen", "Ps"), ("Sprichwörter", "Spr"),
+    ("Kohelet", "Koh"), ("Hoheslied", "Hld"), ("Jesaja", "Jes"), ("Jeremia", "Jer"),
+    ("Klagelieder", "Klgl"), ("Ezechiel", "Ez"), ("Daniel", "Dan"), ("Hosea", "Hos"),
+    ("Joel", "Joel"), ("Amos", "Am"), ("Obadja", "Obd"), ("Jona", "Jona"),
+    ("Micha", "Mi"), ("Nahum", "Nah"), ("Habakuk", "Hab"), ("Zefanja", "Zef"),
+    ("Haggai", "Hag"), ("Sacharja", "Sach"), ("Maleachi", "Mal"),
+
+    ("Matthäus", "Mt"), ("Markus", "Mk"), ("Lukas", "Lk"), ("Johannes", "Joh"),
+    ("Apostelgeschichte", "Apg"), ("Römer", "Röm"), ("1 Korinther", "1 Kor"),
+    ("2 Korinther", "2 Kor"), ("Galater", "Gal"), ("Epheser", "Eph"), ("Philipper", "Phil"),
+    ("Kolosser", "Kol"), ("1 Thessalonicher", "1 Thess"), ("2 Thessalonicher", "2 Thess"),
+    ("1 Timotheus", "1 Tim"), ("2 Timotheus", "2 Tim"), ("Titus", "Tit"), ("Philemon", "Phlm"),
+    ("Hebräer", "Hebr"), ("Jakobus", "Jak"), ("1 Petrus", "1 Petr"), ("2 Petrus", "2 Petr"),
+    ("1 Johannes", "1 Joh"), ("2 Johannes", "2 Joh"), ("3 Johannes", "3 Joh"), ("Judas", "Jud"),
+    ("Offenbarung", "Offb"),
+
+    ("1 Nephi", "1 Ne"), ("2 Nephi", "2 Ne"), ("Jakob", "Jakob"), ("Enos", "Enos"),
+    ("Jarom", "Jarom"), ("Omni", "Omni"), ("Worte Mormons", "WMorm"),
+    ("Mosia", "Mos"), ("Alma", "Alma"), ("Helaman", "Hel"), ("3 Nephi", "3 Ne"),
+    ("4 Nephi", "4 Ne"), ("Mormon", "Morm"), ("Ether", "Ether"), ("Moroni", "Moro"),
+
+    ("Lehre und Bündnisse", "LuB"),
+
+    ("Mose", "Mose"), ("Abraham", "Abr"), ("Joseph Smith—Matthäus", "JSMt"),
+    ("Joseph Smith—Lebensgeschichte", "JSLg"), ("Glaubensartikel", "GA"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FormatOptions, ParseOptions, RangeCollection};
+
+    #[test]
+    fn names_are_unambiguous() {
+        // Within a locale, and across locales so that the locale can be detected. The one
+        // exception is "GA", Galatians in French but the Articles of Faith in German.
+        let mut seen: HashMap<String, &BookData> = HashMap::new();
+        for locale in Locale::ALL {
+            for book_data in BOOK_DATA.iter() {
+                let names = [locale.long_name(book_data), locale.short_name(book_data)];
+                for name in names {
+                    let normalized = normalize_book_name(name);
+                    assert!(
+                        ptr::eq(locale.normalized_book_names()[&normalized], book_data),
+                        "{:?} {}",
+                        locale,
+                        name
+                    );
+                    let first = seen.entry(normalized).or_insert(book_data);
+                    assert!(
+                        ptr::eq(*first, book_data) || name == "GA",
+                        "{:?} {}",
+                        locale,
+                        name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn parse_in_locale() {
+        let cases = [
+            (
+                Locale::Spanish,
+                "1 Nefi 3:7; Hel. 5:12; Éter 12:27",
+                "1 Ne. 3:7; Hel. 5:12; Ether 12:27",
+            ),
+            (
+                Locale::Spanish,
+                "Palabras de Mormón 1:7; P. de Morm. 1:8",
+                "W of M 1:7, 8",
+            ),
+            (
+                Locale::Spanish,
+                "Eter 12:27; Mosiah 3:19",
+                "Ether 12:27; Mosiah 3:19",
+            ),
+            (
+                Locale::Spanish,
+                "Juan 3:16; Santiago 1:5",
+                "John 3:16; James 1:5",
+            ),
+            (
+                Locale::Portuguese,
+                "1 Néfi 3:7; Morôni 10:4–5",
+                "1 Ne. 3:7; Moro. 10:4–5",
+            ),
+            (
+                Locale::Portuguese,
+                "Jó 19:25; Regras de Fé 1:13",
+                "Job 19:25; A of F 1:13",
+            ),
+            (
+                Locale::French,
+                "1 Né 3:7; Doctrine et Alliances 121:7",
+                "1 Ne. 3:7; D&C 121:7",
+            ),
+            (
+                Locale::French,
+                "Jn 3:16; Moïse 1:39",
+                "John 3:16; Moses 1:39",
+            ),
+            (
+                Locale::German,
+                "Lehre und Bündnisse 89:18; Mose 1:39",
+                "D&C 89:18; Moses 1:39",
+            ),
+            (
+                Locale::German,
+                "1 Kön 3:9; Offb 21:4",
+                "1 Kgs. 3:9; Rev. 21:4",
+            ),
+            (Locale::English, "Ether 12:27", "Ether 12:27"),
+        ];
+        for (locale, input, expected) in cases {
+            let options = ParseOptions::new().with_locale(locale);
+            let parsed = RangeCollection::parse_with(input, &options).unwrap();
+            assert_eq!(parsed.to_string(), expected, "{:?} {}", locale, input);
+
+            let detected =
+                RangeCollection::parse_with(input, &ParseOptions::new().with_any_locale());
+            assert_eq!(detected.unwrap().to_string(), expected, "{}", input);
+        }
+
+        // Names from other locales aren't recognized unless asked for.
+        assert!("1 Nefi 3:7".parse::<RangeCollection>().is_err());
+        let spanish = ParseOptions::new().with_locale(Locale::Spanish);
+        assert!(RangeCollection::parse_with("Revelation 1:1", &spanish).is_err());
+    }
+
+    #[test]
+    fn display_in_locale() {
+        let references: RangeCollection =
+            "1 Ne. 3:7–9; W of M 1:7; Ether 12:27; D&C 121; JS—H 1:17"
+                .parse()
+                .unwrap();
+        let cases = [
+            (
+                Locale::English,
+                "1 Ne. 3:7–9; W of M 1:7; Ether 12:27; D&C 121; JS—H 1:17",
+            ),
+            (
+                Locale::Spanish,
+                "1 Ne. 3:7–9; P. de Morm. 1:7; Éter 12:27; DyC 121; JS—H 1:17",
+            ),
+            (
+                Locale::Portuguese,
+                "1 Né. 3:7–9; P. de Mórm. 1:7; Éter 12:27; D&C 121; JS—H 1:17",
+            ),
+            (
+                Locale::French,
+                "1 Né 3:7–9; P de M 1:7; Éth 12:27; D&A 121; JS—H 1:17",
+            ),
+            (
+                Locale::German,
+                "1 Ne 3:7–9; WMorm 1:7; Ether 12:27; LuB 121; JSLg 1:17",
+            ),
+        ];
+        for (locale, expected) in cases {
+            let options = FormatOptions::new().with_locale(locale);
+            let formatted = references.display_with(options).to_string();
+            assert_eq!(formatted, expected, "{:?}", locale);
+
+            // Localized citations read back in the same locale.
+            let parse_options = ParseOptions::new().with_locale(locale);
+            let reparsed = RangeCollection::parse_with(&formatted, &parse_options).unwrap();
+            assert_eq!(reparsed, references, "{:?}", locale);
+        }
+
+        let full = FormatOptions::new()
+            .with_locale(Locale::Spanish)
+            .with_style(crate::CitationStyle::FullNames);
+        assert_eq!(
+            references.display_with(full).to_string(),
+            "1 Nefi 3:7–9; Palabras de Mormón 1:7; Éter 12:27; Doctrina y Convenios 121; \
+                José Smith—Historia 1:17"
+        );
+        let ascii = FormatOptions::new()
+            .with_locale(Locale::Portuguese)
+            .with_style(crate::CitationStyle::Ascii);
+        assert_eq!(
+            references.display_with(ascii).to_string(),
+            "1 Ne. 3:7-9; P. de Morm. 1:7; Eter 12:27; D&C 121; JS-H 1:17"
+        );
+    }
+
+    #[test]
+    fn locale_names() {
+        let cases = [
+            ("en", Ok(Locale::English)),
+            ("spa", Ok(Locale::Spanish)),
+            ("pt-BR", Ok(Locale::Portuguese)),
+            ("French", Ok(Locale::French)),
+            ("de_DE", Ok(Locale::German)),
+            ("xx", Err(UnknownLocale("xx".to_string()))),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<Locale>(), expected, "{}", input);
+        }
+
+        let bom = BOM::from_default_parser().unwrap();
+        assert_eq!(bom.locale(), Some(Locale::English));
+    }
+}
@@ -1,6 +1,6 @@
 /// Parser for the [Gutenberg English BOM](http://www.gutenberg.org/ebooks/17) text.
 pub mod gutenberg {
-    use crate::{BOMParser, Book, Chapter, Verse, WitnessTestimony, BOM};
+    use crate::{BOMParser, Book, Chapter, Verse, WitnessTestimony, Work, BOM};
     use once_cell::sync::Lazy;
     use regex::Regex;
     use std::{borrow::Cow, fs, io, path};
@@ -89,7 +89,7 @@ pub mod gutenberg {
             Self { path: None }
         }
 
-        fn corpus_text(&self) -> Result<Cow<str>, ParseError> {
+        fn corpus_text(&self) -> Result<Cow<'_, str>, ParseError> {
             match &self.path {
                 None => {
                     let s = include_str!("../data/gutenberg.txt");
@@ -109,6 +109,7 @@ pub mod gutenberg {
             let s = self.corpus_text()?;
 
             let mut bom = BOM {
+                work: Work::BookOfMormon,
                 title: "The Book of Mormon".to_string(),
                 subtitle: "Another Testament of Jesus Christ".to_string(),
                 translator: "Joseph Smith, Jr.".to_string(),
@@ -127,11 +128,13 @@ pub mod gutenberg {
                         signatures: EIGHT_WITNESS_SIGNATURES.to_string(),
                     },
                 ],
+                chapter_starts: Default::default(),
                 books: vec![],
             };
 
             let chunks: Vec<_> = s
                 .split("\n\n")
+                .flat_map(split_at_verse_headers)
                 .filter_map(|l| {
                     if l.is_empty() {
                         None
@@ -159,6 +162,23 @@ pub mod gutenberg {
         }
     }
 
+    /// The start of a verse: its reference on a line of its own, followed by its number.
+    static VERSE_HEADER: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"\n\d?[ A-Za-z]{4,} \d{1,2}:\d{1,2}\n\s+\d").unwrap());
+
+    /// Split `chunk` before any verse that isn't separated from the text before it by a blank
+    /// line, as happens before 3 Nephi 28:40 in the Gutenberg text.
+    fn split_at_verse_headers(chunk: &str) -> Vec<&str> {
+        let mut pieces = vec![];
+        let mut start = 0;
+        for header in VERSE_HEADER.find_iter(chunk) {
+            pieces.push(&chunk[start..header.start()]);
+            start = header.start() + 1; // Skip the newline.
+        }
+        pieces.push(&chunk[start..]);
+        pieces
+    }
+
     fn update_book_with_chunk(
         s: &str,
         previous_chunk: &ChunkType,
@@ -196,7 +216,10 @@ pub mod gutenberg {
             ChunkType::ChapterStart => match previous_chunk {
                 ChunkType::BookTitle | ChunkType::BookDescription | ChunkType::Verse { .. } => {
                     if let Some(book) = bom.books.last_mut() {
-                        book.chapters.push(Chapter { verses: vec![] });
+                        book.chapters.push(Chapter {
+                            verses: vec![],
+                            headings: vec![],
+                        });
                     }
                 }
                 _ => {
@@ -221,7 +244,10 @@ pub mod gutenberg {
                         {
                             // Books with only 1 chapter don't have a chapter start, so insert it here.
                             if let Some(book) = bom.books.last_mut() {
-                                book.chapters.push(Chapter { verses: vec![] });
+                                book.chapters.push(Chapter {
+                                    verses: vec![],
+                                    headings: vec![],
+                                });
                             }
                         }
 
@@ -235,7 +261,10 @@ pub mod gutenberg {
                             }
 
                             let v = verse.replace('\n', " ");
-                            chapter.verses.push(Verse { text: v })
+                            chapter.verses.push(Verse {
+                                text: v,
+                                footnotes: vec![],
+                            })
                         }
                     }
                     _ => {
@@ -374,5 +403,1133 @@ SAMUEL H. SMITH";
             let parser = Parser::new(path::Path::new("testdata/bad_data_file.txt"));
             assert!(parser.parse().is_err())
         }
+
+        #[test]
+        fn verse_without_blank_line_before() {
+            let bom = Parser::from_default_corpus().parse().unwrap();
+            let chapter = "3 Ne. 28".parse().unwrap();
+            let verses: Vec<_> = bom.verses_matching(&chapter).map(|v| v.text).collect();
+            assert_eq!(verses.len(), 40);
+            assert!(verses[38].ends_with("could not hold them."));
+            assert!(verses[39].starts_with("And in this state they were to remain"));
+        }
+    }
+}
+
+/// Parser for the [Project Gutenberg King James Bible](http://www.gutenberg.org/ebooks/10)
+/// text. The Old and New Testaments are separate works, so each is parsed on its own.
+pub mod kjv {
+    use crate::reference::BOOK_DATA;
+    use crate::{BOMParser, Book, Chapter, Verse, Work, BOM};
+    use once_cell::sync::Lazy;
+    use regex::Regex;
+    use std::{fs, io, path};
+    use thiserror::Error;
+
+    /// Errors when parsing the King James Bible text.
+    #[derive(Error, Debug)]
+    pub enum ParseError {
+        #[error("KJV corpus not found")]
+        CorpusNotFound {
+            #[from]
+            source: io::Error,
+        },
+
+        #[error("Corpus invalid: {0}")]
+        CorpusInvalid(String),
+
+        #[error("{0:?} isn't part of the Bible")]
+        NotInBible(Work),
+    }
+
+    /// Does the work of parsing.
+    pub struct Parser {
+        path: path::PathBuf,
+        work: Work,
+    }
+
+    impl Parser {
+        /// Path to the Gutenberg King James Bible, and which testament of it to parse:
+        /// `Work::OldTestament` or `Work::NewTestament`. Each book starts with its title as
+        /// printed in the Gutenberg text, such as "The Book of the Prophet Isaiah", followed by
+        /// verses starting with their chapter and verse, such as "29:14". Books missing from
+        /// the text have no chapters.
+        #[must_use]
+        pub fn new(path: &path::Path, work: Work) -> Self {
+            Self {
+                path: path.into(),
+                work,
+            }
+        }
+    }
+
+    impl BOMParser for Parser {
+        type Err = ParseError;
+        fn parse(self) -> Result<BOM, Self::Err> {
+            let (title, titles) = match self.work {
+                Work::OldTestament => ("The Old Testament", &OLD_TESTAMENT_TITLES[..]),
+                Work::NewTestament => ("The New Testament", &NEW_TESTAMENT_TITLES[..]),
+                work => return Err(ParseError::NotInBible(work)),
+            };
+            let text = fs::read_to_string(&self.path)?;
+
+            let mut bom = BOM {
+                work: self.work,
+                title: title.to_string(),
+                subtitle: "King James Version".to_string(),
+                translator: String::new(),
+                last_updated: String::new(),
+                language: "en".to_string(),
+                title_page_text: String::new(),
+                witness_testimonies: vec![],
+                chapter_starts: Default::default(),
+                books: titles
+                    .iter()
+                    .enumerate()
+                    .map(|(book_index, title)| Book {
+                        title: (*title).to_string(),
+                        short_title: BOOK_DATA
+                            .iter()
+                            .find(|d| d.work == self.work && d.book_index == book_index)
+                            .map(|d| d.long_name.to_string()),
+                        description: None,
+                        chapters: vec![],
+                    })
+                    .collect(),
+            };
+
+            let mut title: Option<(Work, usize)> = None; // Most recent book title.
+            let mut book: Option<(Work, usize)> = None; // Book that verses belong to.
+            let mut found_verses = false;
+            for paragraph in body(&text).split("\n\n") {
+                let paragraph = paragraph.split_whitespace().collect::<Vec<_>>().join(" ");
+                if let Some(b) = book_with_title(&paragraph) {
+                    title = Some(b);
+                    continue;
+                }
+
+                // Anything else that doesn't start with a verse, such as the name of a
+                // testament, isn't part of a book.
+                let markers: Vec<_> = VERSE.captures_iter(&paragraph).collect();
+                if markers
+                    .first()
+                    .is_none_or(|m| m.get(0).unwrap().start() != 0)
+                {
+                    continue;
+                }
+
+                for (i, marker) in markers.iter().enumerate() {
+                    let whole = marker.get(0).unwrap(); // Group 0 always exists.
+                    let end = markers
+                        .get(i + 1)
+                        .map_or(paragraph.len(), |next| next.get(0).unwrap().start());
+                    let verse_text = paragraph[whole.end()..end].trim();
+                    let chapter_num: usize = marker["chapter"].parse().unwrap_or(0);
+                    let verse_num: usize = marker["verse"].parse().unwrap_or(0);
+
+                    if (chapter_num, verse_num) == (1, 1) {
+                        book = title.take();
+                    }
+                    let Some((work, book_index)) = book else {
+                        return Err(ParseError::CorpusInvalid(format!(
+                            "Verse before any book title: {}",
+                            paragraph
+                        )));
+                    };
+                    if work != self.work {
+                        continue;
+                    }
+
+                    let chapters = &mut bom.books[book_index].chapters;
+                    if verse_num == 1 && chapter_num == chapters.len() + 1 {
+                        chapters.push(Chapter {
+                            verses: vec![],
+                            headings: vec![],
+                        });
+                    }
+                    let expected = chapters
+                        .last()
+                        .map_or((1, 1), |c| (chapters.len(), c.verses.len() + 1));
+                    if expected != (chapter_num, verse_num) {
+                        return Err(ParseError::CorpusInvalid(format!(
+                            "Parser thought this verse was {}:{} but text says it's {}:{}: {}",
+                            expected.0, expected.1, chapter_num, verse_num, paragraph
+                        )));
+                    }
+
+                    if let Some(chapter) = chapters.last_mut() {
+                        chapter.verses.push(Verse {
+                            text: verse_text.to_string(),
+                            footnotes: vec![],
+                        });
+                    }
+                    found_verses = true;
+                }
+            }
+
+            if !found_verses {
+                return Err(ParseError::CorpusInvalid("No verses found".to_string()));
+            }
+
+            Ok(bom)
+        }
+    }
+
+    /// The chapter and verse starting each verse, such as "29:14 ".
+    static VERSE: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?:^| )(?P<chapter>\d+):(?P<verse>\d+) ").unwrap());
+
+    /// The text between the Project Gutenberg header and footer, or all of `text` if they
+    /// aren't there.
+    fn body(text: &str) -> &str {
+        let start = text
+            .find("*** START OF")
+            .and_then(|i| text[i..].find('\n').map(|n| i + n))
+            .unwrap_or(0);
+        let end = text.find("*** END OF").unwrap_or(text.len()).max(start);
+        &text[start..end]
+    }
+
+    /// The book whose title, as printed in the Gutenberg text, is `paragraph`.
+    fn book_with_title(paragraph: &str) -> Option<(Work, usize)> {
+        let matches = |title: &&str| title.eq_ignore_ascii_case(paragraph);
+        OLD_TESTAMENT_TITLES
+            .iter()
+            .position(matches)
+            .map(|i| (Work::OldTestament, i))
+            .or_else(|| {
+                NEW_TESTAMENT_TITLES
+                    .iter()
+                    .position(matches)
+                    .map(|i| (Work::NewTestament, i))
+            })
+    }
+
+    /// Book titles as printed in the Gutenberg text, with whitespace collapsed, in
+    /// `BOOK_DATA` order.
+    const OLD_TESTAMENT_TITLES: [&str; 39] = [
+        "The First Book of Moses: Called Genesis",
+        "The Second Book of Moses: Called Exodus",
+        "The Third Book of Moses: Called Leviticus",
+        "The Fourth Book of Moses: Called Numbers",
+        "The Fifth Book of Moses: Called Deuteronomy",
+        "The Book of Joshua",
+        "The Book of Judges",
+        "The Book of Ruth",
+        "The First Book of Samuel",
+        "The Second Book of Samuel",
+        "The First Book of the Kings",
+        "The Second Book of the Kings",
+        "The First Book of the Chronicles",
+        "The Second Book of the Chronicles",
+        "Ezra",
+        "The Book of Nehemiah",
+        "The Book of Esther",
+        "The Book of Job",
+        "The Book of Psalms",
+        "The Proverbs",
+        "Ecclesiastes",
+        "The Song of Solomon",
+        "The Book of the Prophet Isaiah",
+        "The Book of the Prophet Jeremiah",
+        "The Lamentations of Jeremiah",
+        "The Book of the Prophet Ezekiel",
+        "The Book of Daniel",
+        "Hosea",
+        "Joel",
+        "Amos",
+        "Obadiah",
+        "Jonah",
+        "Micah",
+        "Nahum",
+        "Habakkuk",
+        "Zephaniah",
+        "Haggai",
+        "Zechariah",
+        "Malachi",
+    ];
+
+    const NEW_TESTAMENT_TITLES: [&str; 27] = [
+        "The Gospel According to Saint Matthew",
+        "The Gospel According to Saint Mark",
+        "The Gospel According to Saint Luke",
+        "The Gospel According to Saint John",
+        "The Acts of the Apostles",
+        "The Epistle of Paul the Apostle to the Romans",
+        "The First Epistle of Paul the Apostle to the Corinthians",
+        "The Second Epistle of Paul the Apostle to the Corinthians",
+        "The Epistle of Paul the Apostle to the Galatians",
+        "The Epistle of Paul the Apostle to the Ephesians",
+        "The Epistle of Paul the Apostle to the Philippians",
+        "The Epistle of Paul the Apostle to the Colossians",
+        "The First Epistle of Paul the Apostle to the Thessalonians",
+        "The Second Epistle of Paul the Apostle to the Thessalonians",
+        "The First Epistle of Paul the Apostle to Timothy",
+        "The Second Epistle of Paul the Apostle to Timothy",
+        "The Epistle of Paul the Apostle to Titus",
+        "The Epistle of Paul the Apostle to Philemon",
+        "The Epistle of Paul the Apostle to the Hebrews",
+        "The General Epistle of James",
+        "The First Epistle General of Peter",
+        "The Second General Epistle of Peter",
+        "The First Epistle General of John",
+        "The Second Epistle General of John",
+        "The Third Epistle General of John",
+        "The General Epistle of Jude",
+        "The Revelation of Saint John the Divine",
+    ];
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::{Library, RangeCollection};
+
+        const SAMPLE: &str = "testdata/kjv_sample.txt";
+
+        fn texts<'a>(bom: &'a BOM, references: &'a RangeCollection) -> Vec<&'a str> {
+            bom.verses_matching(references).map(|v| v.text).collect()
+        }
+
+        #[test]
+        fn old_testament() {
+            let bom = Parser::new(path::Path::new(SAMPLE), Work::OldTestament)
+                .parse()
+                .unwrap();
+            assert_eq!(bom.work(), Work::OldTestament);
+            assert_eq!(bom.books().len(), 39);
+            assert_eq!(bom.books()[0].short_title(), Some("Genesis"));
+            assert_eq!(bom.books()[0].chapters().len(), 2);
+            assert!(bom.books()[1].chapters().is_empty());
+            assert_eq!(bom.verses().count(), 10);
+
+            let references = "Gen. 1:2; Ruth 1:1".parse().unwrap();
+            assert_eq!(
+                texts(&bom, &references),
+                vec![
+                    "And the earth was without form, and void; and darkness was upon the face \
+                    of the deep. And the Spirit of God moved upon the face of the waters.",
+                    "Now it came to pass in the days when the judges ruled, that there was a \
+                    famine in the land. And a certain man of Bethlehemjudah went to sojourn in \
+                    the country of Moab, he, and his wife, and his two sons.",
+                ]
+            );
+            assert_eq!(bom.verses_matching(&"Gen. 2".parse().unwrap()).count(), 3);
+            assert!(!"Matt. 1:1"
+                .parse::<RangeCollection>()
+                .unwrap()
+                .is_valid(&bom));
+        }
+
+        #[test]
+        fn new_testament() {
+            let bom = Parser::new(path::Path::new(SAMPLE), Work::NewTestament)
+                .parse()
+                .unwrap();
+            assert_eq!(bom.books().len(), 27);
+
+            // Verses can share a paragraph.
+            let references = "Matt. 2:1; Philem. 1:2–3".parse().unwrap();
+            assert_eq!(
+                texts(&bom, &references),
+                vec![
+                    "Now when Jesus was born in Bethlehem of Judaea in the days of Herod the \
+                    king, behold, there came wise men from the east to Jerusalem,",
+                    "And to our beloved Apphia, and Archippus our fellowsoldier, and to the \
+                    church in thy house:",
+                    "Grace to you, and peace, from God our Father and the Lord Jesus Christ.",
+                ]
+            );
+        }
+
+        #[test]
+        fn whole_bible_in_library() {
+            let parse = |work| Parser::new(path::Path::new(SAMPLE), work).parse().unwrap();
+            let library = Library::new()
+                .with_work(parse(Work::OldTestament))
+                .with_work(parse(Work::NewTestament))
+                .with_work(BOM::from_default_parser().unwrap());
+            let references = "Gen. 1:1; Matt. 1:1; 1 Ne. 1:1".parse().unwrap();
+            let works: Vec<_> = library
+                .verses_matching(&references)
+                .map(|v| v.reference.work)
+                .collect();
+            assert_eq!(
+                works,
+                vec![Work::OldTestament, Work::NewTestament, Work::BookOfMormon]
+            );
+        }
+
+        #[test]
+        fn errors() {
+            let parse = |file, work| Parser::new(path::Path::new(file), work).parse();
+            assert!(matches!(
+                parse(SAMPLE, Work::BookOfMormon),
+                Err(ParseError::NotInBible(Work::BookOfMormon))
+            ));
+            assert!(matches!(
+                parse("testing123", Work::OldTestament),
+                Err(ParseError::CorpusNotFound { .. })
+            ));
+            for file in [
+                "testdata/empty_file.txt",
+                "testdata/bad_data_file.txt",
+                "testdata/kjv_verse_out_of_order.txt",
+                "testdata/kjv_verse_without_book.txt",
+            ] {
+                assert!(
+                    matches!(
+                        parse(file, Work::OldTestament),
+                        Err(ParseError::CorpusInvalid(_))
+                    ),
+                    "{}",
+                    file
+                );
+            }
+        }
+    }
+}
+
+/// Parser for [USFM](https://ubsicap.github.io/usfm/) files, the format most translation
+/// projects publish in.
+pub mod usfm {
+    use crate::reference::BOOK_DATA;
+    use crate::{BOMParser, Book, Chapter, Footnote, Heading, Verse, Work, BOM};
+    use once_cell::sync::Lazy;
+    use regex::Regex;
+    use std::{fs, io, path};
+    use thiserror::Error;
+
+    /// Errors when parsing USFM files.
+    #[derive(Error, Debug)]
+    pub enum ParseError {
+        #[error("Couldn't read {}", path.display())]
+        FileNotFound {
+            path: path::PathBuf,
+            source: io::Error,
+        },
+
+        #[error("{}:{line}: {message}", path.display())]
+        Invalid {
+            path: path::PathBuf,
+            line: usize,
+            message: String,
+        },
+
+        #[error("No verses of {0:?} found")]
+        NoVerses(Work),
+    }
+
+    /// Does the work of parsing.
+    pub struct Parser {
+        paths: Vec<path::PathBuf>,
+        work: Work,
+        language: String,
+    }
+
+    impl Parser {
+        /// Paths to USFM files, usually one per book, and which work to read from them. Each
+        /// book starts with an `\id` marker giving its USFM code, such as `GEN` or `MAT`, or
+        /// for works outside the Bible its OSIS name, such as `1NE` or `MORO`. Books of other
+        /// works are skipped, and books missing from the files have no chapters.
+        ///
+        /// Section headings (`\s#`, and `\ms#` at level 1) and footnotes (`\f ... \f*`) are
+        /// kept with their chapters, verses and headings. Cross references, figures, word
+        /// attributes (`\w grace|lemma="grace"\w*`) and character styles such as `\add` are
+        /// dropped, leaving their text. Footnotes anywhere else, such as in a book's title,
+        /// are an error.
+        #[must_use]
+        pub fn new<P: AsRef<path::Path>>(paths: &[P], work: Work) -> Self {
+            Self {
+                paths: paths.iter().map(|p| p.as_ref().into()).collect(),
+                work,
+                language: String::new(),
+            }
+        }
+
+        /// Language code of the text, such as "en". USFM files don't say, so it's empty
+        /// unless set here.
+        #[must_use]
+        pub fn with_language(mut self, language: &str) -> Self {
+            self.language = language.to_string();
+            self
+        }
+    }
+
+    impl BOMParser for Parser {
+        type Err = ParseError;
+        fn parse(self) -> Result<BOM, Self::Err> {
+            let title = match self.work {
+                Work::OldTestament => "The Old Testament",
+                Work::NewTestament => "The New Testament",
+                Work::BookOfMormon => "The Book of Mormon",
+                Work::DoctrineAndCovenants => "The Doctrine and Covenants",
+                Work::PearlOfGreatPrice => "The Pearl of Great Price",
+            };
+
+            let mut bom = BOM {
+                work: self.work,
+                title: title.to_string(),
+                subtitle: String::new(),
+                translator: String::new(),
+                last_updated: String::new(),
+                language: self.language,
+                title_page_text: String::new(),
+                witness_testimonies: vec![],
+                chapter_starts: Default::default(),
+                books: BOOK_DATA
+                    .iter()
+                    .filter(|d| d.work == self.work)
+                    .map(|d| Book {
+                        title: d.long_name.to_string(),
+                        short_title: Some(d.long_name.to_string()),
+                        description: None,
+                        chapters: vec![],
+                    })
+                    .collect(),
+            };
+
+            let mut found_verses = false;
+            for path in &self.paths {
+                let text = fs::read_to_string(path).map_err(|source| ParseError::FileNotFound {
+                    path: path.clone(),
+                    source,
+                })?;
+                found_verses |= Reader::new(path, &mut bom).read(&text)?;
+            }
+
+            if !found_verses {
+                return Err(ParseError::NoVerses(self.work));
+            }
+
+            for book in &mut bom.books {
+                book.title = book.title.trim().to_string();
+                book.description = book.description.take().map(|d| d.trim().to_string());
+                for chapter in &mut book.chapters {
+                    for heading in &mut chapter.headings {
+                        heading.text = heading.text.trim().to_string();
+                    }
+                    for verse in &mut chapter.verses {
+                        verse.text = verse.text.trim_end().to_string();
+                    }
+                }
+            }
+
+            Ok(bom)
+        }
+    }
+
+    /// A marker, such as `\v`, `\+add` or `\f*`.
+    static MARKER: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"\\\+?(?P<name>[A-Za-z0-9]+)(?P<closing>\*?)").unwrap());
+
+    /// Where the text following a marker goes.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum Target {
+        Nowhere,
+        Title,
+        Description,
+        Heading,
+        Verse,
+        FootnoteCaller,
+        FootnoteOrigin,
+        FootnoteText,
+    }
+
+    /// The book being read.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum Current {
+        /// No `\id` yet.
+        Nothing,
+        /// A book of a work other than the one being parsed, which is skipped.
+        OtherWork,
+        Book(usize),
+    }
+
+    /// Reads one USFM file into a `BOM`.
+    struct Reader<'a> {
+        path: &'a path::Path,
+        bom: &'a mut BOM,
+        line: usize,
+        current: Current,
+        target: Target,
+        /// Target to return to at the end of a footnote.
+        outer: Target,
+        /// Marker whose number, such as the chapter number after `\c`, comes next.
+        awaiting_number: Option<&'static str>,
+        /// Headings waiting for the verse they come before.
+        headings: Vec<Heading>,
+        /// The open footnote and the line it started on.
+        footnote: Option<(Footnote, usize)>,
+        /// The open cross reference or figure, which is dropped, and the line it started on.
+        ignoring: Option<(String, usize)>,
+        /// Whether a `\w` word, whose attributes follow a '|', is open.
+        in_word: bool,
+        title_started: bool,
+        found_verses: bool,
+    }
+
+    impl<'a> Reader<'a> {
+        fn new(path: &'a path::Path, bom: &'a mut BOM) -> Self {
+            Self {
+                path,
+                bom,
+                line: 0,
+                current: Current::Nothing,
+                target: Target::Nowhere,
+                outer: Target::Nowhere,
+                awaiting_number: None,
+                headings: vec![],
+                footnote: None,
+                ignoring: None,
+                in_word: false,
+                title_started: false,
+                found_verses: false,
+            }
+        }
+
+        /// Read `text`, returning whether it had any verses of the work being parsed.
+        fn read(mut self, text: &str) -> Result<bool, ParseError> {
+            for (i, line) in text.lines().enumerate() {
+                self.line = i + 1;
+                let mut rest = line;
+                let mut line_start = true;
+                while let Some(marker) = MARKER.captures(rest) {
+                    let whole = marker.get(0).unwrap(); // Group 0 always exists.
+                    self.text(&rest[..whole.start()])?;
+                    line_start &= rest[..whole.start()].trim().is_empty();
+                    let closing = !marker["closing"].is_empty();
+                    self.marker(&marker["name"], closing, line_start)?;
+                    line_start = false;
+
+                    // A single space separates an opening marker from its text.
+                    rest = &rest[whole.end()..];
+                    if !closing {
+                        rest = rest.strip_prefix(char::is_whitespace).unwrap_or(rest);
+                    }
+                }
+                self.text(rest)?;
+
+                if let Some(marker) = self.awaiting_number {
+                    return Err(self.invalid(format!("Missing number after \\{}", marker)));
+                }
+                self.text(" ")?; // Line breaks separate words.
+            }
+
+            if let Some((_, line)) = self.footnote {
+                self.line = line;
+                return Err(self.invalid("Footnote isn't closed with \\f*".to_string()));
+            }
+            if let Some((name, line)) = self.ignoring.take() {
+                self.line = line;
+                return Err(self.invalid(format!("\\{} isn't closed with \\{}*", name, name)));
+            }
+
+            Ok(self.found_verses)
+        }
+
+        fn invalid(&self, message: String) -> ParseError {
+            ParseError::Invalid {
+                path: self.path.into(),
+                line: self.line,
+                message,
+            }
+        }
+
+        fn marker(
+            &mut self,
+            name: &str,
+            closing: bool,
+            line_start: bool,
+        ) -> Result<(), ParseError> {
+            if let Some((ignored, _)) = &self.ignoring {
+                if closing && name == ignored {
+                    self.ignoring = None;
+                }
+                return Ok(());
+            }
+
+            match self.current {
+                _ if name == "id" => {
+                    self.awaiting_number = Some("id");
+                    return Ok(());
+                }
+                Current::Nothing => {
+                    return Err(self.invalid(format!("Expected \\id before \\{}", name)));
+                }
+                Current::OtherWork => return Ok(()),
+                Current::Book(_) => {}
+            }
+
+            match name {
+                "c" | "v" if !closing => {
+                    self.awaiting_number = Some(if name == "c" { "c" } else { "v" });
+                }
+                "f" | "fe" if !closing => {
+                    if self.footnote.is_some() {
+                        return Err(self.invalid("Footnote inside another footnote".to_string()));
+                    }
+                    let note = Footnote {
+                        offset: 0,
+                        caller: String::new(),
+                        origin: None,
+                        text: String::new(),
+                    };
+                    self.footnote = Some((note, self.line));
+                    self.outer = self.target;
+                    self.target = Target::FootnoteCaller;
+                }
+                "f" | "fe" => self.close_footnote()?,
+                "fr" if !closing && self.footnote.is_some() => self.target = Target::FootnoteOrigin,
+                _ if name.starts_with('f') && !closing && self.footnote.is_some() => {
+                    self.target = Target::FootnoteText;
+                }
+                "w" => self.in_word = !closing,
+                _ if closing => {} // The end of a character style.
+                "x" | "ex" | "fig" | "rq" => self.ignoring = Some((name.to_string(), self.line)),
+                _ if heading_level(name).is_some() => {
+                    let level = heading_level(name).unwrap_or(1);
+                    self.headings.push(Heading {
+                        level,
+                        verse: 0, // Set at the next verse.
+                        text: String::new(),
+                        footnotes: vec![],
+                    });
+                    self.target = Target::Heading;
+                }
+                "mt" | "mt1" | "mt2" | "mt3" | "mt4" => self.target = Target::Title,
+                "ip" => self.target = Target::Description,
+                _ if PARAGRAPHS.contains(&name.trim_end_matches(char::is_numeric)) => {
+                    // Text after a paragraph break continues the last verse, if any.
+                    let in_verse = self.chapter_mut().is_some_and(|c| !c.verses.is_empty());
+                    self.target = if in_verse {
+                        Target::Verse
+                    } else {
+                        Target::Nowhere
+                    };
+                }
+                // Other markers at the start of a line, such as `\toc1` or `\rem`, are
+                // paragraphs that aren't part of the text. Others are character styles.
+                _ if line_start => self.target = Target::Nowhere,
+                _ => {}
+            }
+            Ok(())
+        }
+
+        fn text(&mut self, text: &str) -> Result<(), ParseError> {
+            let mut text = text;
+            if let Some(marker) = self.awaiting_number {
+                let trimmed = text.trim_start();
+                if trimmed.is_empty() {
+                    return Ok(());
+                }
+                let (number, rest) =
+                    trimmed.split_at(trimmed.find(char::is_whitespace).unwrap_or(trimmed.len()));
+                self.awaiting_number = None;
+                self.number(marker, number)?;
+                text = rest.strip_prefix(char::is_whitespace).unwrap_or(rest);
+            }
+
+            if self.ignoring.is_some() || !matches!(self.current, Current::Book(_)) {
+                if self.current == Current::Nothing && !text.trim().is_empty() {
+                    return Err(self.invalid("Expected \\id before text".to_string()));
+                }
+                return Ok(());
+            }
+
+            // Drop word attributes, as in `\w gracious|lemma="grace"\w*`.
+            let text = match text.split_once('|') {
+                Some((word, _)) if self.in_word => word,
+                _ => text,
+            };
+            match self.target {
+                Target::Nowhere => {}
+                Target::Title => {
+                    let started = std::mem::replace(&mut self.title_started, true);
+                    let title = &mut self.book_mut().title;
+                    if !started {
+                        title.clear();
+                    }
+                    append(title, text);
+                }
+                Target::Description => {
+                    let description = self.book_mut().description.get_or_insert_with(String::new);
+                    append(description, text);
+                }
+                Target::Heading => {
+                    if let Some(heading) = self.headings.last_mut() {
+                        append(&mut heading.text, text);
+                    }
+                }
+                Target::Verse => {
+                    if let Some(verse) = self.chapter_mut().and_then(|c| c.verses.last_mut()) {
+                        append(&mut verse.text, text);
+                    }
+                }
+                Target::FootnoteCaller => {
+                    let trimmed = text.trim_start();
+                    if let Some((note, _)) = self.footnote.as_mut() {
+                        if let Some(caller) = trimmed.split_whitespace().next() {
+                            note.caller = caller.to_string();
+                            append(&mut note.text, &trimmed[caller.len()..]);
+                            self.target = Target::FootnoteText;
+                        }
+                    }
+                }
+                Target::FootnoteOrigin => {
+                    if let Some((note, _)) = self.footnote.as_mut() {
+                        append(note.origin.get_or_insert_with(String::new), text);
+                    }
+                }
+                Target::FootnoteText => {
+                    if let Some((note, _)) = self.footnote.as_mut() {
+                        append(&mut note.text, text);
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        /// Handle the number after `marker`, such as the book code after `\id` or the verse
+        /// number after `\v`.
+        fn number(&mut self, marker: &str, number: &str) -> Result<(), ParseError> {
+            if marker == "id" {
+                let (work, book_index) = book_with_code(number)
+                    .ok_or_else(|| self.invalid(format!("Unknown book code {}", number)))?;
+                self.current = Current::OtherWork;
+                self.target = Target::Nowhere;
+                self.headings.clear();
+                self.title_started = false;
+                if work == self.bom.work {
+                    if !self.bom.books[book_index].chapters.is_empty() {
+                        return Err(self.invalid(format!("Book {} appears twice", number)));
+                    }
+                    self.current = Current::Book(book_index);
+                }
+                return Ok(());
+            }
+
+            let kind = if marker == "c" { "chapter" } else { "verse" };
+            let number: usize = number
+                .parse()
+                .map_err(|_| self.invalid(format!("Invalid {} number {}", kind, number)))?;
+            let expected = if marker == "c" {
+                self.book_mut().chapters.len() + 1
+            } else {
+                let Some(chapter) = self.chapter_mut() else {
+                    return Err(self.invalid("Verse before any chapter".to_string()));
+                };
+                chapter.verses.len() + 1
+            };
+            if number != expected {
+                return Err(self.invalid(format!(
+                    "Expected {} {} but found {}",
+                    kind, expected, number
+                )));
+            }
+
+            if marker == "c" {
+                self.book_mut().chapters.push(Chapter {
+                    verses: vec![],
+                    headings: vec![],
+                });
+                self.target = Target::Nowhere;
+                return Ok(());
+            }
+
+            let headings: Vec<_> = self
+                .headings
+                .drain(..)
+                .map(|heading| Heading {
+                    verse: number,
+                    ..heading
+                })
+                .collect();
+            let chapter = self.book_mut().chapters.last_mut().unwrap(); // Checked above.
+            chapter.headings.extend(headings);
+            chapter.verses.push(Verse {
+                text: String::new(),
+                footnotes: vec![],
+            });
+            self.target = Target::Verse;
+            self.found_verses = true;
+            Ok(())
+        }
+
+        fn close_footnote(&mut self) -> Result<(), ParseError> {
+            let Some((mut note, line)) = self.footnote.take() else {
+                return Err(self.invalid("\\f* without \\f".to_string()));
+            };
+            note.text = note.text.trim().to_string();
+            note.origin = note
+                .origin
+                .map(|o| o.trim().to_string())
+                .filter(|o| !o.is_empty());
+            self.target = self.outer;
+
+            let attached_to = match self.target {
+                Target::Verse => self
+                    .chapter_mut()
+                    .and_then(|c| c.verses.last_mut())
+                    .map(|v| (&v.text, &mut v.footnotes)),
+                Target::Heading => self
+                    .headings
+                    .last_mut()
+                    .map(|h| (&h.text, &mut h.footnotes)),
+                _ => None,
+            };
+            let Some((text, footnotes)) = attached_to else {
+                self.line = line;
+                return Err(self.invalid("Footnote outside a verse or heading".to_string()));
+            };
+            note.offset = text.trim_end().len();
+            footnotes.push(note);
+            Ok(())
+        }
+
+        /// The book being read. Only called while reading a book of the work being parsed.
+        fn book_mut(&mut self) -> &mut Book {
+            let Current::Book(index) = self.current else {
+                unreachable!("Not reading a book");
+            };
+            &mut self.bom.books[index]
+        }
+
+        fn chapter_mut(&mut self) -> Option<&mut Chapter> {
+            self.book_mut().chapters.last_mut()
+        }
+    }
+
+    /// Append `text` to `dest`, collapsing whitespace.
+    fn append(dest: &mut String, text: &str) {
+        let words: Vec<_> = text.split_whitespace().collect();
+        let space_before = text.starts_with(char::is_whitespace);
+        if space_before && !dest.is_empty() && !dest.ends_with(' ') {
+            dest.push(' ');
+        }
+        dest.push_str(&words.join(" "));
+        if !words.is_empty() && text.ends_with(char::is_whitespace) {
+            dest.push(' ');
+        }
+    }
+
+    /// Level of the heading started by `marker`: `\s` and `\s1` are level 1, `\s2` level 2
+    /// and so on. Major section headings (`\ms#`) are level 1.
+    fn heading_level(marker: &str) -> Option<usize> {
+        if let Some(digits) = marker.strip_prefix("ms") {
+            return (digits.is_empty() || digits.parse::<usize>().is_ok()).then_some(1);
+        }
+        match marker.strip_prefix('s')? {
+            "" => Some(1),
+            digits => digits.parse().ok().filter(|&level| level > 0),
+        }
+    }
+
+    /// Paragraph markers, without any trailing level number, whose text is part of a verse.
+    const PARAGRAPHS: [&str; 22] = [
+        "p", "m", "po", "pr", "cls", "pmo", "pm", "pmc", "pmr", "pi", "mi", "nb", "pc", "ph", "b",
+        "q", "qr", "qc", "qa", "qm", "li", "lf",
+    ];
+
+    /// The work and book index of the book with USFM code or OSIS name `code`.
+    fn book_with_code(code: &str) -> Option<(Work, usize)> {
+        let matches = |c: &&str| c.eq_ignore_ascii_case(code);
+        BIBLE_CODES
+            .iter()
+            .position(matches)
+            .map(|i| match i.checked_sub(OLD_TESTAMENT_BOOKS) {
+                None => (Work::OldTestament, i),
+                Some(i) => (Work::NewTestament, i),
+            })
+            .or_else(|| {
+                BOOK_DATA
+                    .iter()
+                    .find(|d| d.osis_name.eq_ignore_ascii_case(code))
+                    .map(|d| (d.work, d.book_index))
+            })
+    }
+
+    const OLD_TESTAMENT_BOOKS: usize = 39;
+
+    /// USFM book codes of the Old and New Testaments, in `BOOK_DATA` order.
+    const BIBLE_CODES: [&str; 66] = [
+        "GEN", "EXO", "LEV", "NUM", "DEU", "JOS", "JDG", "RUT", "1SA", "2SA", "1KI", "2KI", "1CH",
+        "2CH", "EZR", "NEH", "EST", "JOB", "PSA", "PRO", "ECC", "SNG", "ISA", "JER", "LAM", "EZK",
+        "DAN", "HOS", "JOL", "AMO", "OBA", "JON", "MIC", "NAM", "HAB", "ZEP", "HAG", "ZEC", "MAL",
+        "MAT", "MRK", "LUK", "JHN", "ACT", "ROM", "1CO", "2CO", "GAL", "EPH", "PHP", "COL", "1TH",
+        "2TH", "1TI", "2TI", "TIT", "PHM", "HEB", "JAS", "1PE", "2PE", "1JN", "2JN", "3JN", "JUD",
+        "REV",
+    ];
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::RangeCollection;
+
+        const FILES: [&str; 3] = [
+            "testdata/usfm/01-GEN.usfm",
+            "testdata/usfm/40-MAT.usfm",
+            "testdata/usfm/1NE.usfm",
+        ];
+
+        fn texts(bom: &BOM, references: &str) -> Vec<String> {
+            let references: RangeCollection = references.parse().unwrap();
+            bom.verses_matching(&references)
+                .map(|v| v.text.to_string())
+                .collect()
+        }
+
+        #[test]
+        fn old_testament() {
+            let bom = Parser::new(&FILES, Work::OldTestament).parse().unwrap();
+            assert_eq!(bom.title(), "The Old Testament");
+            assert_eq!(bom.books().len(), 39);
+            assert_eq!(bom.verses().count(), 4);
+
+            let genesis = &bom.books()[0];
+            assert_eq!(genesis.title(), "The First Book of Moses Called Genesis");
+            assert_eq!(genesis.short_title(), Some("Genesis"));
+            assert_eq!(genesis.description(), Some("The book of beginnings."));
+            assert!(bom.books()[1].chapters().is_empty());
+
+            // Character styles and cross references are dropped, and verses continue across
+            // lines.
+            assert_eq!(
+                texts(&bom, "Gen. 1:2–3; Gen. 2:1"),
+                vec![
+                    "And the earth was without form, and void; and darkness was upon the face \
+                    of the deep.",
+                    "And God said, Let there be light: and there was light.",
+                    "Thus the heavens and the earth were finished, and all the host of them.",
+                ]
+            );
+
+            let chapter = genesis.chapter(1).unwrap();
+            let headings: Vec<_> = chapter
+                .headings()
+                .iter()
+                .map(|h| (h.level(), h.verse(), h.text()))
+                .collect();
+            assert_eq!(
+                headings,
+                vec![(1, 1, "The Creation"), (2, 3, "The First Day")]
+            );
+
+            let verse = chapter.verse(2).unwrap();
+            let footnotes = verse.footnotes();
+            assert_eq!(footnotes.len(), 1);
+            assert_eq!(
+                &verse.text()[..footnotes[0].offset()],
+                "And the earth was without form, and void;"
+            );
+            assert_eq!(footnotes[0].caller(), "+");
+            assert_eq!(footnotes[0].origin(), Some("1:2"));
+            assert_eq!(footnotes[0].text(), "Hebrew without form waste.");
+            assert!(chapter.verse(1).unwrap().footnotes().is_empty());
+        }
+
+        #[test]
+        fn new_testament() {
+            let bom = Parser::new(&FILES, Work::NewTestament).parse().unwrap();
+            assert_eq!(bom.books().len(), 27);
+            assert_eq!(
+                bom.books()[0].title(),
+                "The Gospel According to Saint Matthew"
+            );
+            assert_eq!(
+                texts(&bom, "Matt. 2:1"),
+                vec!["And seeing the multitudes, he went up into a mountain."]
+            );
+            assert!(bom.books()[0].chapter(1).unwrap().headings().is_empty());
+        }
+
+        #[test]
+        fn book_of_mormon() {
+            let bom = Parser::new(&FILES, Work::BookOfMormon)
+                .with_language("en")
+                .parse()
+                .unwrap();
+            assert_eq!(bom.language(), "en");
+            assert_eq!(bom.books().len(), 15);
+
+            // Word attributes are dropped.
+            assert_eq!(
+                texts(&bom, "1 Ne. 1:1"),
+                vec![
+                    "I, Nephi, having been born of goodly parents, therefore I was taught \
+                    somewhat in all the learning of my father."
+                ]
+            );
+        }
+
+        #[test]
+        fn heading_footnotes_and_bars() {
+            let bom = Parser::new(&["testdata/usfm/02-EXO.usfm"], Work::OldTestament)
+                .parse()
+                .unwrap();
+            let chapter = bom.books()[1].chapter(1).unwrap();
+
+            let heading = &chapter.headings()[0];
+            assert_eq!(heading.text(), "The Children of Israel in Egypt");
+            let footnotes = heading.footnotes();
+            assert_eq!(footnotes.len(), 1);
+            assert_eq!(
+                &heading.text()[..footnotes[0].offset()],
+                "The Children of Israel"
+            );
+            assert_eq!(footnotes[0].text(), "Or, sons of Israel.");
+
+            // Only `\w` words have attributes after a '|'.
+            assert_eq!(
+                texts(&bom, "Ex. 1:1–2"),
+                vec![
+                    "Now these are the names | of the children of Israel, which came into Egypt;",
+                    "Reuben, Simeon, Levi, and Judah.",
+                ]
+            );
+            let verse = chapter.verse(1).unwrap();
+            assert_eq!(verse.footnotes()[0].text(), "A bar | in a note.");
+        }
+
+        #[test]
+        fn errors() {
+            assert!(matches!(
+                Parser::new(&["testing123"], Work::OldTestament).parse(),
+                Err(ParseError::FileNotFound { .. })
+            ));
+            assert!(matches!(
+                Parser::new(&FILES[2..], Work::OldTestament).parse(),
+                Err(ParseError::NoVerses(Work::OldTestament))
+            ));
+
+            let cases = [
+                (
+                    "testdata/usfm/bad_verse_number.usfm",
+                    "testdata/usfm/bad_verse_number.usfm:5: Expected verse 2 but found 3",
+                ),
+                (
+                    "testdata/usfm/unknown_book.usfm",
+                    "testdata/usfm/unknown_book.usfm:1: Unknown book code XYZ",
+                ),
+                (
+                    "testdata/usfm/verse_before_chapter.usfm",
+                    "testdata/usfm/verse_before_chapter.usfm:2: Verse before any chapter",
+                ),
+                (
+                    "testdata/usfm/footnote_in_title.usfm",
+                    "testdata/usfm/footnote_in_title.usfm:2: Footnote outside a verse or heading",
+                ),
+                (
+                    "testdata/bad_data_file.txt",
+                    "testdata/bad_data_file.txt:1: Expected \\id before text",
+                ),
+            ];
+            for (file, expected) in cases {
+                let err = Parser::new(&[file], Work::OldTestament)
+                    .parse()
+                    .unwrap_err();
+                assert!(matches!(err, ParseError::Invalid { .. }), "{}", file);
+                assert_eq!(err.to_string(), expected);
+            }
+        }
     }
 }
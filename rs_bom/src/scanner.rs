@@ -0,0 +1,152 @@
+use crate::reference::{RangeCollection, BOOK_DATA};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::ops::Range;
+
+/// A citation found while scanning free-form text.
+#[derive(Debug)]
+pub struct ReferenceMatch {
+    /// Byte range of the citation within the scanned text.
+    pub range: Range<usize>,
+    /// The parsed citation.
+    pub references: RangeCollection,
+}
+
+impl RangeCollection {
+    /// Find every citation embedded in `text`, such as the references in
+    /// "as Alma taught (Alma 32:21, 27) and later in Moro. 10:4–5".
+    ///
+    /// A citation is a full or abbreviated book name followed by chapters and verses in the
+    /// same format accepted by `RangeCollection::new`. Semicolon-separated citations that
+    /// follow one another are returned as a single match. Book names without a following
+    /// chapter ("as Alma taught") are ignored.
+    pub fn find_all(text: &str) -> impl Iterator<Item = ReferenceMatch> + '_ {
+        CITATION.find_iter(text).filter_map(|m| {
+            m.as_str().parse().ok().map(|references| ReferenceMatch {
+                range: m.range(),
+                references,
+            })
+        })
+    }
+}
+
+static CITATION: Lazy<Regex> = Lazy::new(|| {
+    // Longest names first so that "1 John" is preferred over "John", etc.
+    let mut names: Vec<_> = BOOK_DATA
+        .iter()
+        .flat_map(|d| [d.long_name, d.short_name])
+        .collect();
+    names.sort_by_key(|n| std::cmp::Reverse(n.len()));
+    names.dedup();
+    let book = names
+        .iter()
+        .map(|n| regex::escape(n))
+        .collect::<Vec<_>>()
+        .join("|");
+
+    // Verses may have a part suffix, as in "21b" or "21a–b".
+    let range = r"\d+[a-e]?(?:ff?|\s*[-–—]\s*(?:end|\d+(?:\s*:\s*\d+)?[a-e]?|[a-e]\b))?";
+    let chunks = format!(r"{range}(?:\s*,\s*{range})*");
+    let chapter_verses = format!(r"(?:\d+\s*:\s*{chunks}|{chunks})");
+    Regex::new(&format!(
+        r"\b(?:{book})\s+{chapter_verses}(?:\s*;\s*(?:(?:{book})\s+)?{chapter_verses})*\b"
+    ))
+    .unwrap()
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(text: &str) -> Vec<(&str, String)> {
+        RangeCollection::find_all(text)
+            .map(|m| (&text[m.range.clone()], m.references.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn find_in_prose() {
+        let text = "As Alma taught (Alma 32:21, 27) and later in Moro. 10:4–5, faith is not \
+            a perfect knowledge.";
+        assert_eq!(
+            found(text),
+            vec![
+                ("Alma 32:21, 27", "Alma 32:21, 27".to_string()),
+                ("Moro. 10:4–5", "Moro. 10:4–5".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn find_prefers_longest_book_name() {
+        assert_eq!(
+            found("See 1 John 4:8, Words of Mormon 1:7 and 3 Nephi 11."),
+            vec![
+                ("1 John 4:8", "1 Jn. 4:8".to_string()),
+                ("Words of Mormon 1:7", "W of M 1:7".to_string()),
+                ("3 Nephi 11", "3 Ne. 11".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn find_citation_lists() {
+        assert_eq!(
+            found("[1 Ne. 3:7; Alma 3:5–4:2; 5]"),
+            vec![(
+                "1 Ne. 3:7; Alma 3:5–4:2; 5",
+                "1 Ne. 3:7; Alma 3:5–4:2; 5".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn find_open_ended_ranges() {
+        assert_eq!(
+            found("Read Alma 32:21ff and Moro. 10:3–end, then Ether 12f."),
+            vec![
+                ("Alma 32:21ff", "Alma 32:21ff".to_string()),
+                ("Moro. 10:3–end", "Moro. 10:3ff".to_string()),
+                ("Ether 12f", "Ether 12–13".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn find_verse_parts() {
+        assert_eq!(
+            found("See Alma 32:21a–b and Moro. 10:4b–5; but not Alma 32abc."),
+            vec![
+                ("Alma 32:21a–b", "Alma 32:21a–b".to_string()),
+                ("Moro. 10:4b–5", "Moro. 10:4b–5".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn find_other_standard_works() {
+        assert_eq!(
+            found("Compare D&C 121:7–8 with JS—H 1:17 and Moses 1:39."),
+            vec![
+                ("D&C 121:7–8", "D&C 121:7–8".to_string()),
+                ("JS—H 1:17", "JS—H 1:17".to_string()),
+                ("Moses 1:39", "Moses 1:39".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn find_nothing() {
+        assert!(found("").is_empty());
+        assert!(found("Alma and Amulek taught in Ammonihah.").is_empty());
+        assert!(found("Alma 32abc").is_empty());
+        assert!(found("Ephraim 1:1").is_empty());
+    }
+
+    #[test]
+    fn find_byte_ranges_after_multibyte_text() {
+        let text = "“Mosiah 3:19” — König";
+        let m = RangeCollection::find_all(text).next().unwrap();
+        assert_eq!(&text[m.range], "Mosiah 3:19");
+    }
+}
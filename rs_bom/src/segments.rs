@@ -0,0 +1,112 @@
+use std::ops::Range;
+
+/// Characters that end a sentence.
+const SENTENCE_ENDS: [char; 3] = ['.', '?', '!'];
+/// Characters that end a clause, used to split verses made of a single sentence.
+const CLAUSE_ENDS: [char; 2] = [';', ':'];
+/// Closing punctuation that may follow the end of a sentence or clause.
+const CLOSING: [char; 6] = ['"', '\'', '”', '’', ')', ']'];
+
+/// Split the text of a verse into the segments cited by verse parts, so that "Alma 32:21a"
+/// is the first segment of verse 21 and "Alma 32:21b" the second. Segments are the sentences
+/// of the verse or, if it's a single sentence, its clauses ending in ';' or ':'. Whitespace
+/// between segments is dropped.
+/// ```
+/// use rs_bom::verse_segments;
+///
+/// assert_eq!(
+///     verse_segments("And I said: Behold, I go. And he went; and I followed."),
+///     vec!["And I said: Behold, I go.", "And he went; and I followed."]
+/// );
+/// assert_eq!(
+///     verse_segments("And my father dwelt in a tent; and he was blessed."),
+///     vec!["And my father dwelt in a tent;", "and he was blessed."]
+/// );
+/// ```
+#[must_use]
+pub fn verse_segments(text: &str) -> Vec<&str> {
+    segment_bounds(text).into_iter().map(|r| &text[r]).collect()
+}
+
+/// Byte ranges of each of the `verse_segments` of `text`.
+pub(crate) fn segment_bounds(text: &str) -> Vec<Range<usize>> {
+    let sentences = split_after(text, &SENTENCE_ENDS);
+    if sentences.len() > 1 {
+        sentences
+    } else {
+        split_after(text, &CLAUSE_ENDS)
+    }
+}
+
+/// Split `text` after each of `ends` (and any closing punctuation) that's followed by
+/// whitespace, trimming whitespace from each piece.
+fn split_after(text: &str, ends: &[char]) -> Vec<Range<usize>> {
+    let mut bounds = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((_, c)) = chars.next() {
+        if !ends.contains(&c) {
+            continue;
+        }
+        while let Some(&(_, next)) = chars.peek() {
+            if !CLOSING.contains(&next) {
+                break;
+            }
+            chars.next();
+        }
+        if let Some(&(end, next)) = chars.peek() {
+            if next.is_whitespace() {
+                push_trimmed(text, start..end, &mut bounds);
+                start = end;
+            }
+        }
+    }
+    push_trimmed(text, start..text.len(), &mut bounds);
+    bounds
+}
+
+fn push_trimmed(text: &str, range: Range<usize>, bounds: &mut Vec<Range<usize>>) {
+    let piece = &text[range.clone()];
+    let trimmed = piece.trim_start();
+    let start = range.start + piece.len() - trimmed.len();
+    let end = start + trimmed.trim_end().len();
+    if start < end {
+        bounds.push(start..end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segments() {
+        let cases: [(&str, &[&str]); 6] = [
+            ("", &[]),
+            (
+                "And my father dwelt in a tent.",
+                &["And my father dwelt in a tent."],
+            ),
+            (
+                "Yea, and are ye stripped of pride? I say unto you, Nay! Behold, ye are not.",
+                &[
+                    "Yea, and are ye stripped of pride?",
+                    "I say unto you, Nay!",
+                    "Behold, ye are not.",
+                ],
+            ),
+            (
+                "He said: \"Go ye.\" And they went.",
+                &["He said: \"Go ye.\"", "And they went."],
+            ),
+            (
+                "And he said: Hearken; for I speak.",
+                &["And he said:", "Hearken;", "for I speak."],
+            ),
+            ("  Amen.  ", &["Amen."]),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(verse_segments(text), expected, "{}", text);
+        }
+    }
+}
@@ -0,0 +1,452 @@
+use crate::reference::Work;
+use crate::BOM;
+use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
+
+/// The number of chapters in each book and verses in each chapter of a work. References are
+/// validated, counted and iterated against a `Versification`.
+///
+/// Both a parsed `BOM` and the built-in `StandardVersification` tables implement this, so
+/// references can be resolved with or without loading a corpus.
+pub trait Versification {
+    /// Number of chapters in a book, or `None` if the book doesn't exist.
+    fn chapter_count(&self, work: Work, book_index: usize) -> Option<usize>;
+
+    /// Number of verses in a chapter, or `None` if the chapter doesn't exist. Chapters are
+    /// numbered from 1.
+    fn verse_count(&self, work: Work, book_index: usize, chapter: usize) -> Option<usize>;
+
+    /// Number of verses in a work before the start of a chapter, which is the 0-based
+    /// position of the chapter's first verse. Returns `None` if the chapter doesn't exist.
+    ///
+    /// The default adds up every chapter before it, so implementations that can should look
+    /// it up instead, as `StandardVersification` and `BOM` do.
+    fn verses_before(&self, work: Work, book_index: usize, chapter: usize) -> Option<usize> {
+        self.verse_count(work, book_index, chapter)?;
+        let verses_in_book = |book_index: usize, chapters: usize| -> usize {
+            (1..=chapters)
+                .filter_map(|c| self.verse_count(work, book_index, c))
+                .sum()
+        };
+        let previous_books: usize = (0..book_index)
+            .filter_map(|b| Some(verses_in_book(b, self.chapter_count(work, b)?)))
+            .sum();
+        Some(previous_books + verses_in_book(book_index, chapter - 1))
+    }
+
+    /// The book and chapter holding the verse at 0-based position `ordinal` in `work`, along
+    /// with the position of the chapter's first verse. Returns `None` if the work doesn't have
+    /// that many verses.
+    ///
+    /// Like `verses_before`, the default walks every chapter before it.
+    fn chapter_at(&self, work: Work, ordinal: usize) -> Option<(usize, usize, usize)> {
+        let mut first = 0;
+        for book_index in 0.. {
+            let chapters = self.chapter_count(work, book_index)?;
+            for chapter in 1..=chapters {
+                let verses = self.verse_count(work, book_index, chapter)?;
+                if ordinal < first + verses {
+                    return Some((book_index, chapter, first));
+                }
+                first += verses;
+            }
+        }
+        None
+    }
+}
+
+/// The position of the first verse of every chapter of a work, counting from the start of the
+/// work, so that `Versification::verses_before` and `Versification::chapter_at` are lookups.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ChapterStarts {
+    /// Position of the first verse of each chapter, by book and then chapter.
+    books: Vec<Vec<usize>>,
+    /// Position of the first verse, book index and chapter of each chapter that has verses,
+    /// in order.
+    chapters: Vec<(usize, usize, usize)>,
+    /// Number of verses in the work.
+    verses: usize,
+}
+
+impl ChapterStarts {
+    pub(crate) fn new(versification: &dyn Versification, work: Work) -> Self {
+        let mut starts = Self::default();
+        for book_index in 0.. {
+            let Some(chapters) = versification.chapter_count(work, book_index) else {
+                break;
+            };
+            let mut book = Vec::with_capacity(chapters);
+            for chapter in 1..=chapters {
+                let verses = versification
+                    .verse_count(work, book_index, chapter)
+                    .unwrap_or(0);
+                book.push(starts.verses);
+                if verses > 0 {
+                    starts.chapters.push((starts.verses, book_index, chapter));
+                }
+                starts.verses += verses;
+            }
+            starts.books.push(book);
+        }
+        starts
+    }
+
+    fn verses_before(&self, book_index: usize, chapter: usize) -> Option<usize> {
+        self.books
+            .get(book_index)?
+            .get(chapter.checked_sub(1)?)
+            .copied()
+    }
+
+    fn chapter_at(&self, ordinal: usize) -> Option<(usize, usize, usize)> {
+        if ordinal >= self.verses {
+            return None;
+        }
+        let index = self
+            .chapters
+            .partition_point(|(first, _, _)| *first <= ordinal);
+        let (first, book_index, chapter) = self.chapters[index - 1];
+        Some((book_index, chapter, first))
+    }
+}
+
+/// Chapter and verse counts for every book of every `Work`, embedded in the crate. The Bible
+/// follows the King James Version and the other works follow the 2013 edition of the
+/// Latter-day Saint scriptures.
+/// ```
+/// use rs_bom::{RangeCollection, StandardVersification};
+///
+/// let references: RangeCollection = "Gen. 1; Matt. 5:3–12".parse().unwrap();
+/// assert!(references.is_valid(&StandardVersification));
+/// assert_eq!(references.verse_refs(&StandardVersification).len(), 31 + 10);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StandardVersification;
+
+static STANDARD_CHAPTER_STARTS: Lazy<BTreeMap<Work, ChapterStarts>> = Lazy::new(|| {
+    [
+        Work::OldTestament,
+        Work::NewTestament,
+        Work::BookOfMormon,
+        Work::DoctrineAndCovenants,
+        Work::PearlOfGreatPrice,
+    ]
+    .into_iter()
+    .map(|work| (work, ChapterStarts::new(&StandardVersification, work)))
+    .collect()
+});
+
+impl StandardVersification {
+    fn book(work: Work, book_index: usize) -> Option<&'static [usize]> {
+        let books: &[&[usize]] = match work {
+            Work::OldTestament => &OLD_TESTAMENT,
+            Work::NewTestament => &NEW_TESTAMENT,
+            Work::BookOfMormon => &BOOK_OF_MORMON,
+            Work::DoctrineAndCovenants => &DOCTRINE_AND_COVENANTS,
+            Work::PearlOfGreatPrice => &PEARL_OF_GREAT_PRICE,
+        };
+        books.get(book_index).copied()
+    }
+}
+
+impl Versification for StandardVersification {
+    fn chapter_count(&self, work: Work, book_index: usize) -> Option<usize> {
+        Self::book(work, book_index).map(<[usize]>::len)
+    }
+
+    fn verse_count(&self, work: Work, book_index: usize, chapter: usize) -> Option<usize> {
+        Self::book(work, book_index)?
+            .get(chapter.checked_sub(1)?)
+            .copied()
+    }
+
+    fn verses_before(&self, work: Work, book_index: usize, chapter: usize) -> Option<usize> {
+        STANDARD_CHAPTER_STARTS
+            .get(&work)?
+            .verses_before(book_index, chapter)
+    }
+
+    fn chapter_at(&self, work: Work, ordinal: usize) -> Option<(usize, usize, usize)> {
+        STANDARD_CHAPTER_STARTS.get(&work)?.chapter_at(ordinal)
+    }
+}
+
+impl BOM {
+    fn chapter_starts(&self) -> &ChapterStarts {
+        self.chapter_starts
+            .get_or_init(|| ChapterStarts::new(self, self.work))
+    }
+}
+
+impl Versification for BOM {
+    fn chapter_count(&self, work: Work, book_index: usize) -> Option<usize> {
+        if work != self.work {
+            return None;
+        }
+        self.books.get(book_index).map(|b| b.chapters.len())
+    }
+
+    fn verse_count(&self, work: Work, book_index: usize, chapter: usize) -> Option<usize> {
+        if work != self.work {
+            return None;
+        }
+        self.books
+            .get(book_index)?
+            .chapters
+            .get(chapter.checked_sub(1)?)
+            .map(|c| c.verses.len())
+    }
+
+    fn verses_before(&self, work: Work, book_index: usize, chapter: usize) -> Option<usize> {
+        if work != self.work {
+            return None;
+        }
+        self.chapter_starts().verses_before(book_index, chapter)
+    }
+
+    fn chapter_at(&self, work: Work, ordinal: usize) -> Option<(usize, usize, usize)> {
+        if work != self.work {
+            return None;
+        }
+        self.chapter_starts().chapter_at(ordinal)
+    }
+}
+
+/// Verses in each chapter of each book of the Old Testament, in `BOOK_DATA` order.
+#[rustfmt::skip]
+const OLD_TESTAMENT: [&[usize]; 39] = [
+    &[31, 25, 24, 26, 32, 22, 24, 22, 29, 32, 32, 20, 18, 24, 21, 16, 27, 33, 38, 18, 34, 24, 20, 67, 34, 35, 46, 22, 35, 43, 55, 32, 20, 31, 29, 43, 36, 30, 23, 23, 57, 38, 34, 34, 28, 34, 31, 22, 33, 26], // Genesis
+    &[22, 25, 22, 31, 23, 30, 25, 32, 35, 29, 10, 51, 22, 31, 27, 36, 16, 27, 25, 26, 36, 31, 33, 18, 40, 37, 21, 43, 46, 38, 18, 35, 23, 35, 35, 38, 29, 31, 43, 38], // Exodus
+    &[17, 16, 17, 35, 19, 30, 38, 36, 24, 20, 47, 8, 59, 57, 33, 34, 16, 30, 37, 27, 24, 33, 44, 23, 55, 46, 34], // Leviticus
+    &[54, 34, 51, 49, 31, 27, 89, 26, 23, 36, 35, 16, 33, 45, 41, 50, 13, 32, 22, 29, 35, 41, 30, 25, 18, 65, 23, 31, 40, 16, 54, 42, 56, 29, 34, 13], // Numbers
+    &[46, 37, 29, 49, 33, 25, 26, 20, 29, 22, 32, 32, 18, 29, 23, 22, 20, 22, 21, 20, 23, 30, 25, 22, 19, 19, 26, 68, 29, 20, 30, 52, 29, 12], // Deuteronomy
+    &[18, 24, 17, 24, 15, 27, 26, 35, 27, 43, 23, 24, 33, 15, 63, 10, 18, 28, 51, 9, 45, 34, 16, 33], // Joshua
+    &[36, 23, 31, 24, 31, 40, 25, 35, 57, 18, 40, 15, 25, 20, 20, 31, 13, 31, 30, 48, 25], // Judges
+    &[22, 23, 18, 22], // Ruth
+    &[28, 36, 21, 22, 12, 21, 17, 22, 27, 27, 15, 25, 23, 52, 35, 23, 58, 30, 24, 42, 15, 23, 29, 22, 44, 25, 12, 25, 11, 31, 13], // 1 Samuel
+    &[27, 32, 39, 12, 25, 23, 29, 18, 13, 19, 27, 31, 39, 33, 37, 23, 29, 33, 43, 26, 22, 51, 39, 25], // 2 Samuel
+    &[53, 46, 28, 34, 18, 38, 51, 66, 28, 29, 43, 33, 34, 31, 34, 34, 24, 46, 21, 43, 29, 53], // 1 Kings
+    &[18, 25, 27, 44, 27, 33, 20, 29, 37, 36, 21, 21, 25, 29, 38, 20, 41, 37, 37, 21, 26, 20, 37, 20, 30], // 2 Kings
+    &[54, 55, 24, 43, 26, 81, 40, 40, 44, 14, 47, 40, 14, 17, 29, 43, 27, 17, 19, 8, 30, 19, 32, 31, 31, 32, 34, 21, 30], // 1 Chronicles
+    &[17, 18, 17, 22, 14, 42, 22, 18, 31, 19, 23, 16, 22, 15, 19, 14, 19, 34, 11, 37, 20, 12, 21, 27, 28, 23, 9, 27, 36, 27, 21, 33, 25, 33, 27, 23], // 2 Chronicles
+    &[11, 70, 13, 24, 17, 22, 28, 36, 15, 44], // Ezra
+    &[11, 20, 32, 23, 19, 19, 73, 18, 38, 39, 36, 47, 31], // Nehemiah
+    &[22, 23, 15, 17, 14, 14, 10, 17, 32, 3], // Esther
+    &[22, 13, 26, 21, 27, 30, 21, 22, 35, 22, 20, 25, 28, 22, 35, 22, 16, 21, 29, 29, 34, 30, 17, 25, 6, 14, 23, 28, 25, 31, 40, 22, 33, 37, 16, 33, 24, 41, 30, 24, 34, 17], // Job
+    &[6, 12, 8, 8, 12, 10, 17, 9, 20, 18, 7, 8, 6, 7, 5, 11, 15, 50, 14, 9, 13, 31, 6, 10, 22, 12, 14, 9, 11, 12, 24, 11, 22, 22, 28, 12, 40, 22, 13, 17, 13, 11, 5, 26, 17, 11, 9, 14, 20, 23, 19, 9, 6, 7, 23, 13, 11, 11, 17, 12, 8, 12, 11, 10, 13, 20, 7, 35, 36, 5, 24, 20, 28, 23, 10, 12, 20, 72, 13, 19, 16, 8, 18, 12, 13, 17, 7, 18, 52, 17, 16, 15, 5, 23, 11, 13, 12, 9, 9, 5, 8, 28, 22, 35, 45, 48, 43, 13, 31, 7, 10, 10, 9, 8, 18, 19, 2, 29, 176, 7, 8, 9, 4, 8, 5, 6, 5, 6, 8, 8, 3, 18, 3, 3, 21, 26, 9, 8, 24, 13, 10, 7, 12, 15, 21, 10, 20, 14, 9, 6], // Psalms
+    &[33, 22, 35, 27, 23, 35, 27, 36, 18, 32, 31, 28, 25, 35, 33, 33, 28, 24, 29, 30, 31, 29, 35, 34, 28, 28, 27, 28, 27, 33, 31], // Proverbs
+    &[18, 26, 22, 16, 20, 12, 29, 17, 18, 20, 10, 14], // Ecclesiastes
+    &[17, 17, 11, 16, 16, 13, 13, 14], // Song of Solomon
+    &[31, 22, 26, 6, 30, 13, 25, 22, 21, 34, 16, 6, 22, 32, 9, 14, 14, 7, 25, 6, 17, 25, 18, 23, 12, 21, 13, 29, 24, 33, 9, 20, 24, 17, 10, 22, 38, 22, 8, 31, 29, 25, 28, 28, 25, 13, 15, 22, 26, 11, 23, 15, 12, 17, 13, 12, 21, 14, 21, 22, 11, 12, 19, 12, 25, 24], // Isaiah
+    &[19, 37, 25, 31, 31, 30, 34, 22, 26, 25, 23, 17, 27, 22, 21, 21, 27, 23, 15, 18, 14, 30, 40, 10, 38, 24, 22, 17, 32, 24, 40, 44, 26, 22, 19, 32, 21, 28, 18, 16, 18, 22, 13, 30, 5, 28, 7, 47, 39, 46, 64, 34], // Jeremiah
+    &[22, 22, 66, 22, 22], // Lamentations
+    &[28, 10, 27, 17, 17, 14, 27, 18, 11, 22, 25, 28, 23, 23, 8, 63, 24, 32, 14, 49, 32, 31, 49, 27, 17, 21, 36, 26, 21, 26, 18, 32, 33, 31, 15, 38, 28, 23, 29, 49, 26, 20, 27, 31, 25, 24, 23, 35], // Ezekiel
+    &[21, 49, 30, 37, 31, 28, 28, 27, 27, 21, 45, 13], // Daniel
+    &[11, 23, 5, 19, 15, 11, 16, 14, 17, 15, 12, 14, 16, 9], // Hosea
+    &[20, 32, 21], // Joel
+    &[15, 16, 15, 13, 27, 14, 17, 14, 15], // Amos
+    &[21], // Obadiah
+    &[17, 10, 10, 11], // Jonah
+    &[16, 13, 12, 13, 15, 16, 20], // Micah
+    &[15, 13, 19], // Nahum
+    &[17, 20, 19], // Habakkuk
+    &[18, 15, 20], // Zephaniah
+    &[15, 23], // Haggai
+    &[21, 13, 10, 14, 11, 15, 14, 23, 17, 12, 17, 14, 9, 21], // Zechariah
+    &[14, 17, 18, 6], // Malachi
+];
+
+/// Verses in each chapter of each book of the New Testament, in `BOOK_DATA` order.
+#[rustfmt::skip]
+const NEW_TESTAMENT: [&[usize]; 27] = [
+    &[25, 23, 17, 25, 48, 34, 29, 34, 38, 42, 30, 50, 58, 36, 39, 28, 27, 35, 30, 34, 46, 46, 39, 51, 46, 75, 66, 20], // Matthew
+    &[45, 28, 35, 41, 43, 56, 37, 38, 50, 52, 33, 44, 37, 72, 47, 20], // Mark
+    &[80, 52, 38, 44, 39, 49, 50, 56, 62, 42, 54, 59, 35, 35, 32, 31, 37, 43, 48, 47, 38, 71, 56, 53], // Luke
+    &[51, 25, 36, 54, 47, 71, 53, 59, 41, 42, 57, 50, 38, 31, 27, 33, 26, 40, 42, 31, 25], // John
+    &[26, 47, 26, 37, 42, 15, 60, 40, 43, 48, 30, 25, 52, 28, 41, 40, 34, 28, 41, 38, 40, 30, 35, 27, 27, 32, 44, 31], // Acts
+    &[32, 29, 31, 25, 21, 23, 25, 39, 33, 21, 36, 21, 14, 23, 33, 27], // Romans
+    &[31, 16, 23, 21, 13, 20, 40, 13, 27, 33, 34, 31, 13, 40, 58, 24], // 1 Corinthians
+    &[24, 17, 18, 18, 21, 18, 16, 24, 15, 18, 33, 21, 14], // 2 Corinthians
+    &[24, 21, 29, 31, 26, 18], // Galatians
+    &[23, 22, 21, 32, 33, 24], // Ephesians
+    &[30, 30, 21, 23], // Philippians
+    &[29, 23, 25, 18], // Colossians
+    &[10, 20, 13, 18, 28], // 1 Thessalonians
+    &[12, 17, 18], // 2 Thessalonians
+    &[20, 15, 16, 16, 25, 21], // 1 Timothy
+    &[18, 26, 17, 22], // 2 Timothy
+    &[16, 15, 15], // Titus
+    &[25], // Philemon
+    &[14, 18, 19, 16, 14, 20, 28, 13, 28, 39, 40, 29, 25], // Hebrews
+    &[27, 26, 18, 17, 20], // James
+    &[25, 25, 22, 19, 14], // 1 Peter
+    &[21, 22, 18], // 2 Peter
+    &[10, 29, 24, 21, 21], // 1 John
+    &[13], // 2 John
+    &[14], // 3 John
+    &[25], // Jude
+    &[20, 29, 22, 11, 14, 17, 17, 13, 21, 11, 19, 17, 18, 20, 8, 21, 18, 24, 21, 15, 27, 21], // Revelation
+];
+
+/// Verses in each chapter of each book of the Book of Mormon, in `BOOK_DATA` order.
+#[rustfmt::skip]
+const BOOK_OF_MORMON: [&[usize]; 15] = [
+    &[20, 24, 31, 38, 22, 6, 22, 38, 6, 22, 36, 23, 42, 30, 36, 39, 55, 25, 24, 22, 26, 31], // 1 Nephi
+    &[32, 30, 25, 35, 34, 18, 11, 25, 54, 25, 8, 22, 26, 6, 30, 13, 25, 22, 21, 34, 16, 6, 22, 32, 30, 33, 35, 32, 14, 18, 21, 9, 15], // 2 Nephi
+    &[19, 35, 14, 18, 77, 13, 27], // Jacob
+    &[27], // Enos
+    &[15], // Jarom
+    &[30], // Omni
+    &[18], // Words of Mormon
+    &[18, 41, 27, 30, 15, 7, 33, 21, 19, 22, 29, 37, 35, 12, 31, 15, 20, 35, 29, 26, 36, 16, 39, 25, 24, 39, 37, 20, 47], // Mosiah
+    &[33, 38, 27, 20, 62, 8, 27, 32, 34, 32, 46, 37, 31, 29, 19, 21, 39, 43, 36, 30, 23, 35, 18, 30, 17, 37, 30, 14, 17, 60, 38, 43, 23, 41, 16, 30, 47, 15, 19, 26, 15, 31, 54, 24, 24, 41, 36, 25, 30, 40, 37, 40, 23, 24, 35, 57, 36, 41, 13, 36, 21, 52, 17], // Alma
+    &[34, 14, 37, 26, 52, 41, 29, 28, 41, 19, 38, 26, 39, 31, 17, 25], // Helaman
+    &[30, 19, 26, 33, 26, 30, 26, 25, 22, 19, 41, 48, 34, 27, 24, 20, 25, 39, 36, 46, 29, 17, 14, 18, 6, 21, 33, 40, 9, 2], // 3 Nephi
+    &[49], // 4 Nephi
+    &[19, 29, 22, 23, 24, 22, 10, 41, 37], // Mormon
+    &[43, 25, 28, 19, 6, 30, 27, 26, 35, 34, 23, 41, 31, 31, 34], // Ether
+    &[4, 3, 4, 3, 2, 9, 48, 30, 26, 34], // Moroni
+];
+
+/// Verses in each section of the Doctrine and Covenants, which is cited as a single book.
+#[rustfmt::skip]
+const DOCTRINE_AND_COVENANTS: [&[usize]; 1] = [
+    &[
+        39, 3, 20, 7, 35, 37, 8, 12, 14, 70, 30, 9, 1, 11, 6, 6, 9, 47, 41, 84,
+        12, 4, 7, 19, 16, 2, 18, 16, 50, 11, 13, 5, 18, 12, 27, 8, 4, 42, 24, 3,
+        12, 93, 35, 6, 75, 33, 4, 6, 28, 46, 20, 44, 7, 10, 6, 20, 16, 65, 24, 17,
+        39, 9, 66, 43, 6, 13, 14, 35, 8, 18, 11, 26, 6, 7, 36, 119, 15, 22, 4, 5,
+        7, 24, 6, 120, 12, 11, 8, 141, 21, 37, 6, 2, 53, 17, 17, 9, 28, 48, 8, 17,
+        101, 34, 40, 86, 41, 8, 100, 8, 80, 16, 11, 34, 10, 2, 19, 1, 16, 6, 7, 1,
+        46, 9, 17, 145, 4, 3, 12, 25, 9, 23, 8, 66, 74, 12, 7, 42, 10, 60,
+    ],
+];
+
+/// Verses in each chapter of each book of the Pearl of Great Price, in `BOOK_DATA` order.
+#[rustfmt::skip]
+const PEARL_OF_GREAT_PRICE: [&[usize]; 5] = [
+    &[42, 31, 25, 32, 59, 68, 69, 30], // Moses
+    &[31, 25, 28, 31, 21], // Abraham
+    &[55], // Joseph Smith—Matthew
+    &[75], // Joseph Smith—History
+    &[13], // Articles of Faith
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reference::BOOK_DATA;
+
+    #[test]
+    fn every_book_has_counts() {
+        for book in BOOK_DATA.iter() {
+            let chapters = StandardVersification
+                .chapter_count(book.work, book.book_index)
+                .unwrap_or_else(|| panic!("No chapters for {}", book.long_name));
+            for chapter in 1..=chapters {
+                assert!(StandardVersification
+                    .verse_count(book.work, book.book_index, chapter)
+                    .is_some_and(|verses| verses > 0));
+            }
+        }
+    }
+
+    #[test]
+    fn totals() {
+        let total = |work: Work, books: usize| -> usize {
+            (0..books)
+                .flat_map(|b| {
+                    let chapters = StandardVersification.chapter_count(work, b).unwrap();
+                    (1..=chapters).map(move |c| (b, c))
+                })
+                .map(|(b, c)| StandardVersification.verse_count(work, b, c).unwrap())
+                .sum()
+        };
+        assert_eq!(total(Work::OldTestament, 39), 23145);
+        assert_eq!(total(Work::NewTestament, 27), 7957);
+        assert_eq!(total(Work::BookOfMormon, 15), 6604);
+        assert_eq!(total(Work::DoctrineAndCovenants, 1), 3654);
+        assert_eq!(total(Work::PearlOfGreatPrice, 5), 635);
+    }
+
+    #[test]
+    fn matches_parsed_bom() {
+        let bom = BOM::from_default_parser().unwrap();
+        for book_index in 0..bom.books.len() {
+            let chapters = bom.chapter_count(Work::BookOfMormon, book_index);
+            assert_eq!(
+                chapters,
+                StandardVersification.chapter_count(Work::BookOfMormon, book_index)
+            );
+            for chapter in 1..=chapters.unwrap() {
+                assert_eq!(
+                    bom.verse_count(Work::BookOfMormon, book_index, chapter),
+                    StandardVersification.verse_count(Work::BookOfMormon, book_index, chapter)
+                );
+            }
+        }
+        assert_eq!(bom.chapter_count(Work::OldTestament, 0), None);
+    }
+
+    /// Only the required methods, so the default `verses_before` and `chapter_at` are used.
+    struct Counts;
+
+    impl Versification for Counts {
+        fn chapter_count(&self, work: Work, book_index: usize) -> Option<usize> {
+            StandardVersification.chapter_count(work, book_index)
+        }
+
+        fn verse_count(&self, work: Work, book_index: usize, chapter: usize) -> Option<usize> {
+            StandardVersification.verse_count(work, book_index, chapter)
+        }
+    }
+
+    #[test]
+    fn chapter_starts_match_defaults() {
+        let bom = BOM::from_default_parser().unwrap();
+        let versifications: [&dyn Versification; 2] = [&StandardVersification, &bom];
+        for versification in versifications {
+            for book in BOOK_DATA.iter() {
+                let Some(chapters) = versification.chapter_count(book.work, book.book_index) else {
+                    continue;
+                };
+                for chapter in 1..=chapters + 1 {
+                    let before = versification.verses_before(book.work, book.book_index, chapter);
+                    assert_eq!(
+                        before,
+                        Counts.verses_before(book.work, book.book_index, chapter),
+                        "{} {}",
+                        book.long_name,
+                        chapter
+                    );
+                    if let Some(before) = before {
+                        assert_eq!(
+                            versification.chapter_at(book.work, before),
+                            Some((book.book_index, chapter, before))
+                        );
+                    }
+                }
+            }
+        }
+
+        assert_eq!(
+            StandardVersification.chapter_at(Work::BookOfMormon, 6603),
+            Some((14, 10, 6604 - 34))
+        );
+        assert_eq!(
+            StandardVersification.chapter_at(Work::BookOfMormon, 6604),
+            None
+        );
+        assert_eq!(Counts.chapter_at(Work::BookOfMormon, 6604), None);
+        assert_eq!(bom.verses_before(Work::OldTestament, 0, 1), None);
+    }
+
+    #[test]
+    fn missing_books_and_chapters() {
+        let v = StandardVersification;
+        assert_eq!(v.chapter_count(Work::OldTestament, 39), None);
+        assert_eq!(v.verse_count(Work::OldTestament, 18, 119), Some(176));
+        assert_eq!(v.verse_count(Work::OldTestament, 18, 151), None);
+        assert_eq!(v.verse_count(Work::NewTestament, 0, 0), None);
+    }
+}
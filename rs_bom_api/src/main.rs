@@ -6,7 +6,9 @@ use rand::Rng;
 use rocket::response::status;
 use rocket::serde::{json::Json, Serialize};
 
-use rs_bom::{RangeCollection, VerseReference, VerseWithReference, Work, BOM};
+use rs_bom::{
+    CitationStyle, RangeCollection, StandardVersification, VerseReference, VerseWithReference, BOM,
+};
 
 lazy_static! {
     static ref STATIC_BOM: BOM =
@@ -25,6 +27,7 @@ struct WebParsedReference {
     original_reference: String,
     parsed_reference: String,
     is_valid: bool,
+    warnings: Vec<String>,
 }
 
 impl<'a> From<VerseWithReference<'a>> for WebVerseWithReference {
@@ -47,7 +50,7 @@ fn single_verse(
     chapter: usize,
     verse: usize,
 ) -> Result<Json<WebVerseWithReference>, status::NotFound<String>> {
-    let reference = VerseReference::new(Work::BookOfMormon, book, chapter, verse);
+    let reference = VerseReference::new(STATIC_BOM.work(), book, chapter, verse);
     STATIC_BOM
         .verse_matching(&reference)
         .map(|v| Json(v.into()))
@@ -58,8 +61,9 @@ fn single_verse(
 fn verses(
     reference_string: String,
 ) -> Result<Json<Vec<WebVerseWithReference>>, status::NotFound<String>> {
-    let reference = RangeCollection::new(&reference_string)
+    let mut reference = RangeCollection::new(&reference_string)
         .map_err(|e| status::NotFound(format!("Error: {}", e)))?;
+    reference.clamp_to(&*STATIC_BOM);
 
     let verses: Vec<_> = STATIC_BOM
         .verses_matching(&reference)
@@ -68,6 +72,20 @@ fn verses(
     Ok(Json(verses))
 }
 
+#[get("/verses/<reference_string>/warnings")]
+fn verses_warnings(
+    reference_string: String,
+) -> Result<Json<Vec<String>>, status::NotFound<String>> {
+    let mut reference = RangeCollection::new(&reference_string)
+        .map_err(|e| status::NotFound(format!("Error: {}", e)))?;
+    let warnings = reference
+        .clamp_to(&*STATIC_BOM)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    Ok(Json(warnings))
+}
+
 #[get("/verse/random")]
 fn random_verse() -> Json<WebVerseWithReference> {
     let verses = STATIC_BOM.verses();
@@ -77,18 +95,35 @@ fn random_verse() -> Json<WebVerseWithReference> {
     Json(random_verse.into())
 }
 
-#[get("/canonicalize/<reference_string>")]
+#[get("/canonicalize/<reference_string>?<style>")]
 fn canonicalize(
     reference_string: String,
+    style: Option<&str>,
 ) -> Result<Json<WebParsedReference>, status::NotFound<String>> {
+    let style = style
+        .map_or(Ok(CitationStyle::default()), str::parse)
+        .map_err(|e| status::NotFound(format!("Error: {}", e)))?;
     let mut collection = RangeCollection::new(&reference_string)
         .map_err(|e| status::NotFound(format!("Error: {}", e)))?;
     collection.canonicalize();
+    let parsed_reference = match style {
+        CitationStyle::Osis => collection
+            .to_osis(&StandardVersification)
+            .map_err(|e| status::NotFound(format!("Error: {}", e)))?,
+        style => collection.format_with(style),
+    };
+    let warnings = collection
+        .clone()
+        .clamp_to(&*STATIC_BOM)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
 
     Ok(Json(WebParsedReference {
         original_reference: reference_string,
-        parsed_reference: collection.to_string(),
-        is_valid: collection.is_valid(&STATIC_BOM),
+        parsed_reference,
+        is_valid: collection.is_valid(&*STATIC_BOM),
+        warnings,
     }))
 }
 
@@ -102,7 +137,13 @@ fn rocket() -> _ {
     rocket::build()
         .mount(
             "/",
-            routes![single_verse, verses, random_verse, canonicalize],
+            routes![
+                single_verse,
+                verses,
+                verses_warnings,
+                random_verse,
+                canonicalize
+            ],
         )
         .register("/", catchers![not_found])
 }
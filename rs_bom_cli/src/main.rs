@@ -62,7 +62,10 @@ fn main() -> Result<()> {
 
             // Try to parse as a reference first.
             let range = RangeCollection::new(&query);
-            if let Ok(range) = range {
+            if let Ok(mut range) = range {
+                for warning in range.clamp_to(&bom) {
+                    eprintln!("Warning: {}", warning);
+                }
                 matches = bom.verses_matching(&range).map(|v| v.to_string()).collect();
                 total_match_count = matches.len();
             } else {
@@ -94,7 +97,8 @@ fn main() -> Result<()> {
 fn get_bom(delete_cache: bool) -> Result<BOM> {
     // Try to de-serialize a cached bincode version (to avoid re-parsing all the source),
     // or if that fails fallback to parsing the source.
-    const TEMP_FILE_NAME: &str = "rs_bom_serialized";
+    // Change the name whenever the parsed text changes, so that older caches aren't used.
+    const TEMP_FILE_NAME: &str = "rs_bom_serialized_2";
     let mut file_path = env::temp_dir();
     file_path.push(TEMP_FILE_NAME);
 